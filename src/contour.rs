@@ -0,0 +1,177 @@
+use num::{Complex, Float};
+
+/// Number of points each edge of a contour is split into before refining
+const INITIAL_STEPS: usize = 32;
+/// Deepest refinement of a single step
+const MAX_DEPTH: usize = 24;
+
+/// An axis-aligned rectangle of the complex plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rectangle<F> {
+    /// Bottom-left corner
+    pub min: Complex<F>,
+    /// Top-right corner
+    pub max: Complex<F>,
+}
+
+impl<F: Float> Rectangle<F> {
+    #[must_use]
+    pub fn new(min: Complex<F>, max: Complex<F>) -> Self {
+        debug_assert!(min.re <= max.re && min.im <= max.im);
+        Self { min, max }
+    }
+
+    #[must_use]
+    pub fn center(&self) -> Complex<F> {
+        (self.min + self.max) / F::from(2).unwrap()
+    }
+
+    #[must_use]
+    pub fn width(&self) -> F {
+        self.max.re - self.min.re
+    }
+
+    #[must_use]
+    pub fn height(&self) -> F {
+        self.max.im - self.min.im
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Complex<F>) -> bool {
+        self.min.re <= point.re
+            && point.re <= self.max.re
+            && self.min.im <= point.im
+            && point.im <= self.max.im
+    }
+
+    /// Grows the rectangle by `margin` on every side
+    #[must_use]
+    pub fn expand(&self, margin: F) -> Self {
+        let m = Complex::new(margin, margin);
+        Self::new(self.min - m, self.max + m)
+    }
+
+    /// The corners in counter-clockwise order, starting from `min`
+    #[must_use]
+    pub const fn corners(&self) -> [Complex<F>; 4] {
+        [
+            self.min,
+            Complex::new(self.max.re, self.min.im),
+            self.max,
+            Complex::new(self.min.re, self.max.im),
+        ]
+    }
+
+    /// Cuts the rectangle across its longest side, at `ratio` of its length
+    #[must_use]
+    pub fn split(&self, ratio: F) -> (Self, Self) {
+        if self.width() >= self.height() {
+            let x = self.min.re + self.width() * ratio;
+            (
+                Self::new(self.min, Complex::new(x, self.max.im)),
+                Self::new(Complex::new(x, self.min.im), self.max),
+            )
+        } else {
+            let y = self.min.im + self.height() * ratio;
+            (
+                Self::new(self.min, Complex::new(self.max.re, y)),
+                Self::new(Complex::new(self.min.re, y), self.max),
+            )
+        }
+    }
+}
+
+/// Counts how many times `f` winds around the origin along the closed polygon `path`
+///
+/// By the argument principle, for an analytic `f` and a counter-clockwise
+/// path this is the number of zeros minus the number of poles enclosed.
+/// Every edge is sampled and each step is halved until the argument changes
+/// by less than π/8, so fast rotations are not missed. Returns `None` when `f`
+/// vanishes (or is not finite) on the path, where the count is undefined. A zero
+/// between two samples shows up as a jump of the argument that no refinement
+/// resolves, which is also rejected.
+pub fn winding_number<F: Float>(
+    f: impl Fn(Complex<F>) -> Complex<F>,
+    path: &[Complex<F>],
+) -> Option<i64> {
+    let mut total = F::zero();
+
+    for (i, &p) in path.iter().enumerate() {
+        let q = path[(i + 1) % path.len()];
+
+        let steps = F::from(INITIAL_STEPS).unwrap();
+        let mut prev = (p, f(p));
+
+        for j in 1..=INITIAL_STEPS {
+            let x = p + (q - p) * (F::from(j).unwrap() / steps);
+            let next = (x, f(x));
+            total = total + arg_change(&f, prev, next, 0)?;
+            prev = next;
+        }
+    }
+
+    let turns = total / F::from(std::f64::consts::TAU).unwrap();
+    turns.round().to_i64()
+}
+
+/// Change of `arg f` along the straight segment between two evaluated points
+fn arg_change<F: Float>(
+    f: &impl Fn(Complex<F>) -> Complex<F>,
+    (p, fp): (Complex<F>, Complex<F>),
+    (q, fq): (Complex<F>, Complex<F>),
+    depth: usize,
+) -> Option<F> {
+    if !is_usable(fp) || !is_usable(fq) {
+        return None;
+    }
+
+    let delta = (fq / fp).arg();
+    let limit = F::from(std::f64::consts::FRAC_PI_8).unwrap();
+
+    if delta.abs() < limit {
+        return Some(delta);
+    }
+    if depth >= MAX_DEPTH {
+        return None;
+    }
+
+    let m = (p + q) / F::from(2).unwrap();
+    let mid = (m, f(m));
+
+    Some(arg_change(f, (p, fp), mid, depth + 1)? + arg_change(f, mid, (q, fq), depth + 1)?)
+}
+
+fn is_usable<F: Float>(x: Complex<F>) -> bool {
+    x.re.is_finite() && x.im.is_finite() && x.norm_sqr() > F::min_positive_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_polynomial_roots() {
+        // (s - 1) (s + 1) (s - 3j)
+        let f = |s: Complex<f64>| (s - 1.0) * (s + 1.0) * (s - Complex::new(0.0, 3.0));
+
+        let all = Rectangle::new(Complex::new(-2.0, -2.0), Complex::new(2.0, 4.0));
+        assert_eq!(winding_number(f, &all.corners()), Some(3));
+
+        let (left, right) = all.split(0.4);
+        assert_eq!(winding_number(f, &left.corners()), Some(2));
+        assert_eq!(winding_number(f, &right.corners()), Some(1));
+    }
+
+    #[test]
+    fn root_on_path() {
+        let f = |s: Complex<f64>| s;
+        let rect = Rectangle::new(Complex::new(0.0, -1.0), Complex::new(1.0, 1.0));
+
+        assert_eq!(winding_number(f, &rect.corners()), None);
+
+        // The zero lies between two samples of the left edge, so the refinement
+        // runs out of depth instead of landing on it
+        let rect = Rectangle::new(Complex::new(0.0, -1.0), Complex::new(1.0, 0.7));
+        assert_eq!(winding_number(f, &rect.corners()), None);
+    }
+}
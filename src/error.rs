@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+/// Errors returned by the fallible entry points of the crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// A polynomial has no coefficients, or they are all zero where a nonzero one is needed
+    EmptyPolynomial,
+    /// A coefficient is NaN or infinite
+    NonFiniteCoefficients,
+    /// The numerator order is too high, e.g. above the denominator order, or equal
+    /// to it for an impulse response
+    ImproperSystem {
+        numerator_order: usize,
+        denominator_order: usize,
+    },
+    /// The root finder produced non-finite roots
+    SolverDivergence,
+    /// The matrices of a state-space model have incompatible shapes
+    DimensionMismatch,
+    /// The conversion between continuous and discrete time cannot be applied to the system
+    UnsupportedConversion,
+    /// Systems with different sample times, or continuous and discrete ones, are connected
+    SampleTimeMismatch,
+    /// An argument is out of its valid range, with the requirement it breaks
+    InvalidParameter(&'static str),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyPolynomial => write!(f, "empty or zero polynomial"),
+            Self::NonFiniteCoefficients => {
+                write!(f, "polynomial with NaN or infinite coefficients")
+            }
+            Self::ImproperSystem {
+                numerator_order,
+                denominator_order,
+            } => write!(
+                f,
+                "improper system, the numerator has order {numerator_order} \
+                 and the denominator has order {denominator_order}"
+            ),
+            Self::SolverDivergence => write!(f, "the root finder diverged"),
+            Self::DimensionMismatch => write!(f, "matrices with incompatible dimensions"),
+            Self::UnsupportedConversion => {
+                write!(f, "the conversion cannot be applied to this system")
+            }
+            Self::SampleTimeMismatch => write!(f, "systems with different sample times"),
+            Self::InvalidParameter(reason) => write!(f, "invalid parameter, {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -4,6 +4,9 @@
 
 use num::Float;
 
+pub mod contour;
+pub mod error;
+pub mod matrix;
 pub mod polynomials;
 pub mod transfer_functions;
 
@@ -21,13 +24,14 @@ impl<F: Float> Eq for NotNanFloat<F> {}
 
 impl<F: Float> PartialOrd for NotNanFloat<F> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(&other.0)
+        Some(self.cmp(other))
     }
 }
 
 impl<F: Float> Ord for NotNanFloat<F> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other)
-            .map_or_else(|| panic!("NotNanFloat was NaN"), |x| x)
+        self.0
+            .partial_cmp(&other.0)
+            .unwrap_or_else(|| panic!("NotNanFloat was NaN"))
     }
 }
@@ -1,14 +1,12 @@
-#![feature(let_chains)]
-mod rng;
 mod plot;
+mod rng;
 
 use clap::{Parser, Subcommand};
 
-
 #[derive(Subcommand, Debug)]
 enum FrontEnd {
     Macroquad,
-    WGPU,
+    Wgpu,
 }
 
 /// Binary that draws a dynamic root-locus plot
@@ -20,15 +18,11 @@ struct Args {
     frontend: FrontEnd,
 }
 
-async fn macroquad_main() {
-    plot::macroquad::mainloop().await;
-}
-
 fn main() {
     let args = Args::parse();
 
     match args.frontend {
-        FrontEnd::WGPU => pollster::block_on(plot::wgpu::run()),
+        FrontEnd::Wgpu => pollster::block_on(plot::wgpu::run()),
         FrontEnd::Macroquad => pollster::block_on(plot::macroquad::mainloop()),
     }
-}
\ No newline at end of file
+}
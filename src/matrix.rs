@@ -0,0 +1,327 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, Index, IndexMut, Mul, Sub},
+};
+
+use num::{Complex, Float};
+
+use crate::polynomials::{roots::RootFinding, Polynomial};
+
+/// Terms of the Taylor series of the exponential, enough for a norm below 1/2
+const TAYLOR_TERMS: usize = 18;
+/// Most halvings of the matrix before its exponential, bounding non-finite norms
+const MAX_SQUARINGS: usize = 1100;
+
+/// A small dense matrix, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<F> {
+    rows: usize,
+    cols: usize,
+    data: Vec<F>,
+}
+
+impl<F: Float> Matrix<F> {
+    /// Builds a matrix from its elements, row by row
+    #[must_use]
+    pub fn new(rows: usize, cols: usize, data: Vec<F>) -> Self {
+        assert_eq!(
+            data.len(),
+            rows * cols,
+            "the data does not match the dimensions"
+        );
+        Self { rows, cols, data }
+    }
+
+    #[must_use]
+    pub fn from_rows(rows: &[&[F]]) -> Self {
+        let cols = rows.first().map_or(0, |x| x.len());
+        assert!(
+            rows.iter().all(|x| x.len() == cols),
+            "the rows must have the same length"
+        );
+
+        Self::new(rows.len(), cols, rows.concat())
+    }
+
+    #[must_use]
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self::new(rows, cols, vec![F::zero(); rows * cols])
+    }
+
+    #[must_use]
+    pub fn identity(n: usize) -> Self {
+        let mut out = Self::zeros(n, n);
+        for i in 0..n {
+            out[(i, i)] = F::one();
+        }
+        out
+    }
+
+    #[must_use]
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub const fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[must_use]
+    pub const fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    #[must_use]
+    pub fn is_finite(&self) -> bool {
+        self.data.iter().all(|x| x.is_finite())
+    }
+
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let mut out = Self::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                out[(j, i)] = self[(i, j)];
+            }
+        }
+        out
+    }
+
+    #[must_use]
+    pub fn trace(&self) -> F {
+        assert!(self.is_square(), "the trace needs a square matrix");
+        (0..self.rows).fold(F::zero(), |acc, i| acc + self[(i, i)])
+    }
+
+    /// Computes `e^A` by scaling and squaring
+    ///
+    /// `A` is halved until its norm is below 1/2, where the Taylor series
+    /// converges quickly, and the result is squared back as many times
+    #[must_use]
+    pub fn exp(&self) -> Self {
+        assert!(self.is_square(), "the exponential needs a square matrix");
+
+        let half = F::from(0.5).unwrap();
+        let norm = (0..self.rows)
+            .map(|i| (0..self.cols).fold(F::zero(), |acc, j| acc + self[(i, j)].abs()))
+            .fold(F::zero(), F::max);
+
+        let mut squarings = 0;
+        let mut scale = F::one();
+        while norm * scale > half && squarings < MAX_SQUARINGS {
+            scale = scale * half;
+            squarings += 1;
+        }
+
+        let scaled = self.clone() * scale;
+        let mut term = Self::identity(self.rows);
+        let mut out = term.clone();
+        for k in 1..=TAYLOR_TERMS {
+            term = (&term * &scaled) * F::from(k).unwrap().recip();
+            out = &out + &term;
+        }
+
+        for _ in 0..squarings {
+            out = &out * &out;
+        }
+        out
+    }
+
+    /// Computes `det(sI - A)` and the matrices `Mₖ` of `adj(sI - A) = Σ Mₖ s^(n-k)`,
+    /// for `k` from 1 to `n`, with the Faddeev-LeVerrier algorithm
+    ///
+    /// `M₁ = I`, `Mₖ = A Mₖ₋₁ + cₙ₋ₖ₊₁ I` and `cₙ₋ₖ = -tr(A Mₖ) / k`, where `cᵢ`
+    /// are the coefficients of the characteristic polynomial. Fine for the small
+    /// orders of plant models, it loses accuracy on large ones.
+    #[must_use]
+    pub fn resolvent(&self) -> (Polynomial<F>, Vec<Self>) {
+        assert!(self.is_square(), "the resolvent needs a square matrix");
+
+        let n = self.rows;
+        let mut coefficients = vec![F::zero(); n + 1];
+        coefficients[n] = F::one();
+
+        let mut adjugate = Vec::with_capacity(n);
+        let mut m = Self::zeros(n, n);
+
+        for k in 1..=n {
+            m = &(self * &m) + &(Self::identity(n) * coefficients[n - k + 1]);
+            coefficients[n - k] = -(self * &m).trace() / F::from(k).unwrap();
+            adjugate.push(m.clone());
+        }
+
+        (Polynomial::new(coefficients), adjugate)
+    }
+
+    /// Computes `det(sI - A)`
+    #[must_use]
+    pub fn characteristic_polynomial(&self) -> Polynomial<F> {
+        self.resolvent().0
+    }
+}
+
+impl<F: Float + Debug> Matrix<F> {
+    /// Computes the eigenvalues as the roots of the characteristic polynomial
+    #[must_use]
+    pub fn eigenvalues(&self) -> Vec<Complex<F>> {
+        let poly = self.characteristic_polynomial();
+
+        let mut out = vec![Complex::new(F::zero(), F::zero()); poly.order()];
+        poly.find_roots(&mut out, F::epsilon());
+        out
+    }
+}
+
+impl<F> Index<(usize, usize)> for Matrix<F> {
+    type Output = F;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(i < self.rows && j < self.cols, "matrix index out of range");
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<F> IndexMut<(usize, usize)> for Matrix<F> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(i < self.rows && j < self.cols, "matrix index out of range");
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<F: Float> Add for &Matrix<F> {
+    type Output = Matrix<F>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        assert_eq!(
+            (self.rows, self.cols),
+            (rhs.rows, rhs.cols),
+            "dimension mismatch"
+        );
+        Matrix::new(
+            self.rows,
+            self.cols,
+            self.data
+                .iter()
+                .zip(&rhs.data)
+                .map(|(&a, &b)| a + b)
+                .collect(),
+        )
+    }
+}
+
+impl<F: Float> Sub for &Matrix<F> {
+    type Output = Matrix<F>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        assert_eq!(
+            (self.rows, self.cols),
+            (rhs.rows, rhs.cols),
+            "dimension mismatch"
+        );
+        Matrix::new(
+            self.rows,
+            self.cols,
+            self.data
+                .iter()
+                .zip(&rhs.data)
+                .map(|(&a, &b)| a - b)
+                .collect(),
+        )
+    }
+}
+
+impl<F: Float> Mul for &Matrix<F> {
+    type Output = Matrix<F>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(self.cols, rhs.rows, "dimension mismatch");
+
+        let mut out = Matrix::zeros(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..rhs.cols {
+                    out[(i, j)] = out[(i, j)] + a * rhs[(k, j)];
+                }
+            }
+        }
+        out
+    }
+}
+
+impl<F: Float> Mul<F> for Matrix<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: F) -> Self::Output {
+        Self::new(
+            self.rows,
+            self.cols,
+            self.data.iter().map(|&x| x * rhs).collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn products() {
+        let a = Matrix::from_rows(&[&[1.0, 2.0], &[3.0, 4.0]]);
+        let b = Matrix::from_rows(&[&[0.0, 1.0], &[1.0, 0.0]]);
+
+        assert_eq!(&a * &b, Matrix::from_rows(&[&[2.0, 1.0], &[4.0, 3.0]]));
+        assert_eq!(&a * &Matrix::identity(2), a);
+        assert_eq!(
+            a.transpose(),
+            Matrix::from_rows(&[&[1.0, 3.0], &[2.0, 4.0]])
+        );
+        assert_eq!(&(&a + &b) - &b, a);
+        assert_abs_diff_eq!(a.trace(), 5.0);
+    }
+
+    #[test]
+    fn exponential() {
+        // Rotation by 2 rad, with a norm large enough to need squarings
+        let a = Matrix::from_rows(&[&[0.0, -2.0], &[2.0, 0.0]]);
+        let e = a.exp();
+
+        let expected =
+            Matrix::from_rows(&[&[2f64.cos(), -(2f64.sin())], &[2f64.sin(), 2f64.cos()]]);
+        for i in 0..2 {
+            for j in 0..2 {
+                assert_abs_diff_eq!(e[(i, j)], expected[(i, j)], epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn characteristic_polynomial() {
+        // Eigenvalues 1, 2 and 3
+        let a = Matrix::from_rows(&[&[2.0, 1.0, 0.0], &[0.0, 1.0, 0.0], &[1.0, 0.0, 3.0]]);
+
+        let poly = a.characteristic_polynomial();
+        assert_eq!(poly, Polynomial::new(vec![-6.0, 11.0, -6.0, 1.0]));
+
+        let mut eigenvalues = a.eigenvalues().iter().map(|x| x.re).collect::<Vec<_>>();
+        eigenvalues.sort_by(f64::total_cmp);
+        for (x, e) in eigenvalues.into_iter().zip([1.0, 2.0, 3.0]) {
+            assert_abs_diff_eq!(x, e, epsilon = 1e-9);
+        }
+
+        // adj(sI - A) (sI - A) = det(sI - A) I, checked at s = 5
+        let (det, adjugate) = a.resolvent();
+        let s = 5.0;
+        let adj = adjugate
+            .iter()
+            .rev()
+            .enumerate()
+            .fold(Matrix::zeros(3, 3), |acc, (i, m)| {
+                &acc + &(m.clone() * s.powi(i32::try_from(i).unwrap()))
+            });
+        let product = &adj * &(&(Matrix::identity(3) * s) - &a);
+        assert_eq!(product, Matrix::identity(3) * det.eval(s));
+    }
+}
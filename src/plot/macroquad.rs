@@ -1,20 +1,136 @@
 use crate::rng::generate_rng;
 use macroquad::prelude::*;
 use num::{Complex, ToPrimitive, Zero};
-use rust_lab::polynomials::{root_locus::RootLocus, roots::PolynomialRoot, Polynomial};
-use std::time::Instant;
+use rust_lab::{
+    contour::Rectangle,
+    polynomials::{
+        root_locus::{
+            angles::{arrival_angles, departure_angles},
+            delay::{DelaySample, PadeDelay},
+            discrete::{damping_spiral, CURVE_POINTS},
+            gain_plots::GainPlotQuantity,
+            options::{RootLocusOptions, Sampling},
+            Domain, RootLocus,
+        },
+        roots::PolynomialRoot,
+        Polynomial,
+    },
+    transfer_functions::time_response::InputSignal,
+};
+use std::time::{Duration, Instant};
+
+/// How long the view must stay still before the exact delay roots are searched again
+const EXACT_IDLE: Duration = Duration::from_millis(250);
+
+/// Steady-state error the gain bounds shown with the stable range are computed for
+const ERROR_BOUND: f32 = 0.05;
+
+/// Inputs whose steady-state errors are shown with the stability overlay
+const ERROR_INPUTS: [(InputSignal, &str); 3] = [
+    (InputSignal::Step, "step"),
+    (InputSignal::Ramp, "ramp"),
+    (InputSignal::Parabola, "parabola"),
+];
+
+const COLORS: [Color; 12] = [
+    RED, GREEN, BLUE, YELLOW, PINK, BROWN, BEIGE, LIME, LIGHTGRAY, PURPLE, ORANGE, MAGENTA,
+];
+
+/// Draws `quantity` against `log k` for every branch in the lower right corner
+fn draw_gain_plot(
+    rl: &RootLocus<f32>,
+    quantity: GainPlotQuantity,
+    filter: Option<usize>,
+    zeta: f32,
+    (min_gain, max_gain): (f32, f32),
+) {
+    let width = screen_width() * 0.43;
+    let height = screen_height() * 0.35;
+    let x0 = screen_width() - width - 10.0;
+    let y0 = screen_height() - height - 40.0;
+
+    let (lmin, lmax) = (min_gain.log10(), max_gain.log10());
+
+    let branches = (0..rl.get_branches())
+        .filter(|&i| filter.is_none_or(|x| x == i))
+        .map(|i| {
+            let points = rl
+                .gain_plot(i)
+                .into_iter()
+                .filter(|x| x.gain >= min_gain && x.gain <= max_gain)
+                .map(|x| (x.gain.log10(), x.value(quantity)))
+                .filter(|(_, y)| y.is_finite())
+                .collect::<Vec<_>>();
+            (i, points)
+        })
+        .collect::<Vec<_>>();
+
+    let (mut ymin, mut ymax) = branches
+        .iter()
+        .flat_map(|(_, x)| x)
+        .fold((f32::INFINITY, -f32::INFINITY), |(a, b), &(_, y)| {
+            (a.min(y), b.max(y))
+        });
+    if ymin > ymax {
+        (ymin, ymax) = (-1.0, 1.0);
+    }
+    let pad = ((ymax - ymin) * 0.05).max(1e-3);
+    (ymin, ymax) = (ymin - pad, ymax + pad);
+
+    let to_screen = |lk: f32, y: f32| {
+        (
+            x0 + (lk - lmin) / (lmax - lmin) * width,
+            y0 + height - (y - ymin) / (ymax - ymin) * height,
+        )
+    };
+
+    draw_rectangle(x0, y0, width, height, BLACK);
+    draw_rectangle_lines(x0, y0, width, height, 1.0, GRAY);
+
+    // Decades of the gain axis
+    for d in (lmin.ceil() as i32)..=(lmax.floor() as i32) {
+        let (x, _) = to_screen(d as f32, ymin);
+        draw_line(x, y0, x, y0 + height, 1.0, DARKGRAY);
+        draw_text(&format!("1e{d}"), x + 2.0, y0 + height - 4.0, 16.0, GRAY);
+    }
+
+    if quantity == GainPlotQuantity::Damping && zeta > ymin && zeta < ymax {
+        let (_, y) = to_screen(lmin, zeta);
+        draw_line(x0, y, x0 + width, y, 1.0, DARKGRAY);
+    }
+    if ymin < 0.0 && ymax > 0.0 {
+        let (_, y) = to_screen(lmin, 0.0);
+        draw_line(x0, y, x0 + width, y, 1.0, GRAY);
+    }
+
+    for (i, points) in &branches {
+        for w in points.windows(2) {
+            let (ax, ay) = to_screen(w[0].0, w[0].1);
+            let (bx, by) = to_screen(w[1].0, w[1].1);
+            draw_line(ax, ay, bx, by, 1.5, COLORS[i % COLORS.len()]);
+        }
+    }
+
+    draw_text(
+        &format!("{} vs k\t\t[{ymin:.3}, {ymax:.3}]", quantity.name()),
+        x0 + 5.0,
+        y0 + 18.0,
+        20.0,
+        WHITE,
+    );
+}
 
 enum Mode {
     Zoom,
     Interval,
     Precision,
+    Damping,
+    Delay,
+    SampleTime,
+    Gain,
 }
 
 pub async fn mainloop() {
-    const COLORS: [Color; 12] = [
-        RED, GREEN, BLUE, YELLOW, PINK, BROWN, BEIGE, LIME, LIGHTGRAY, PURPLE, ORANGE, MAGENTA,
-    ];
-
     let rng = generate_rng(1_000);
 
     let mut tot = 0;
@@ -43,30 +159,100 @@ pub async fn mainloop() {
     let mut mode = Mode::Zoom;
     let mut interval = 0.01;
     let mut precision = 1e-6;
+    let mut zeta = 0.7f32;
     let mut fps = true;
     let mut val_fps = 0;
     let mut last_update_time = Instant::now();
     let mut show_load = false;
     let mut val_load = 0;
+    let mut show_stability = false;
+    let mut delay_enabled = false;
+    let mut delay_time = 0.5f32;
+    let mut pade_order = 2;
+    let mut sample_time = 1.0f32;
+    let mut gain = 1.0f32;
+    let mut show_exact = false;
+    let mut show_sensitivity = false;
+    let mut gain_plot = None;
+    // Exact delay roots, and the view (scales and offsets) they were computed for
+    let mut exact: Vec<DelaySample<f32>> = Vec::new();
+    let mut exact_view = None;
+    // The view of the previous frame, and when it last changed
+    let mut last_view = (sx, sy, ox, oy);
+    let mut view_changed = Instant::now();
+
+    let mut rl = RootLocus::new(
+        Polynomial::from_roots(1.0f32, &a_roots),
+        Polynomial::from_roots(1.0, &b_roots),
+    )
+    .unwrap();
+    // Why the last calculation failed, if it did
+    let mut error = None;
+    // Whether the roots or the sampling changed since the last calculation
+    let mut dirty = true;
+    // Sampling of the last calculation
+    let mut options = RootLocusOptions::default();
 
     loop {
         let t1 = Instant::now();
 
-        let poly_a = Polynomial::from_roots(1.0f32, &a_roots);
-        let poly_b = Polynomial::from_roots(1.0, &b_roots);
+        if dirty {
+            options = RootLocusOptions::default()
+                .with_sampling(Sampling::Geometric {
+                    ratio: 1.0 + interval,
+                })
+                .with_precision(precision)
+                .with_jitter(rng.clone());
+            error = delay_enabled
+                .then(|| PadeDelay::new(delay_time, pade_order))
+                .transpose()
+                .and_then(|delay| {
+                    rl.set_delay(delay);
+                    rl.set_open_loop(
+                        Polynomial::from_roots(1.0, &a_roots),
+                        Polynomial::from_roots(1.0, &b_roots),
+                    )
+                })
+                .and_then(|()| rl.calculate_all(&options))
+                .err();
+            dirty = false;
+            exact_view = None;
+        }
 
-        let mut rl = RootLocus::new(poly_a.clone(), poly_b.clone());
+        // The search is too slow to run on every frame of a pan or zoom
+        if last_view != (sx, sy, ox, oy) {
+            last_view = (sx, sy, ox, oy);
+            view_changed = Instant::now();
+        }
 
-        rl.calculate_all(precision, 1.0 + interval, 0.01, 1000.0, &rng);
+        if show_exact
+            && exact_view != Some((sx, sy, ox, oy))
+            && view_changed.elapsed() >= EXACT_IDLE
+        {
+            // Search the visible part of the plane
+            let region = Rectangle::new(
+                Complex::new(-ox / sx, (screen_height() - oy) / sy),
+                Complex::new((screen_width() - ox) / sx, -oy / sy),
+            );
+            let gains = std::iter::successors(Some(0.01f32), |k| Some(k * 1.3))
+                .take_while(|&k| k < 1000.0)
+                .collect::<Vec<_>>();
 
-        let all_roots = rl.get_roots();
+            exact = rl.exact_delay_locus(&region, &gains);
+            exact_view = Some((sx, sy, ox, oy));
+        }
 
         let mut min_re = f32::INFINITY;
         let mut min_im = f32::INFINITY;
         let mut max_re = -f32::INFINITY;
         let mut max_im = -f32::INFINITY;
 
-        for x in all_roots.iter() {
+        // Roots at infinity have no place in the plot
+        for x in rl
+            .iter_samples()
+            .flat_map(|x| x.roots)
+            .filter(|x| x.re.is_finite() && x.im.is_finite())
+        {
             if x.re < min_re {
                 min_re = x.re;
             }
@@ -85,7 +271,11 @@ pub async fn mainloop() {
             mode = match mode {
                 Mode::Zoom => Mode::Interval,
                 Mode::Interval => Mode::Precision,
-                Mode::Precision => Mode::Zoom,
+                Mode::Precision => Mode::Damping,
+                Mode::Damping => Mode::Delay,
+                Mode::Delay => Mode::SampleTime,
+                Mode::SampleTime => Mode::Gain,
+                Mode::Gain => Mode::Zoom,
             }
         }
         if is_key_pressed(KeyCode::F) {
@@ -94,6 +284,34 @@ pub async fn mainloop() {
         if is_key_pressed(KeyCode::L) {
             show_load = !show_load;
         }
+        if is_key_pressed(KeyCode::K) {
+            show_stability = !show_stability;
+        }
+        if is_key_pressed(KeyCode::D) {
+            delay_enabled = !delay_enabled;
+            dirty = true;
+        }
+        if is_key_pressed(KeyCode::G) {
+            gain_plot = match gain_plot {
+                None => Some(0),
+                Some(_) => None,
+            };
+        }
+        if is_key_pressed(KeyCode::V) {
+            gain_plot = gain_plot.map(|x| (x + 1) % GainPlotQuantity::ALL.len());
+        }
+        if is_key_pressed(KeyCode::T) {
+            show_sensitivity = !show_sensitivity;
+        }
+        if is_key_pressed(KeyCode::E) {
+            show_exact = !show_exact;
+        }
+        if is_key_pressed(KeyCode::Z) {
+            rl.set_domain(match rl.get_domain() {
+                Domain::Continuous => Domain::Discrete { sample_time },
+                Domain::Discrete { .. } => Domain::Continuous,
+            });
+        }
 
         // Scale calculation
 
@@ -139,6 +357,7 @@ pub async fn mainloop() {
                         let nint = interval * val;
                         if !nint.is_zero() && nint.is_finite() {
                             interval = nint;
+                            dirty = true;
                         }
                     }
                 }
@@ -148,6 +367,50 @@ pub async fn mainloop() {
                         let nprc = precision * val;
                         if !nprc.is_zero() && nprc.is_finite() {
                             precision = nprc;
+                            dirty = true;
+                        }
+                    }
+                }
+                Mode::Damping => {
+                    if wheel != 0 {
+                        zeta = 0.01f32
+                            .mul_add(wheel.to_f32().unwrap(), zeta)
+                            .clamp(0.0, 1.0);
+                    }
+                }
+                Mode::Delay => {
+                    if wheel != 0 {
+                        let ndel = delay_time * 1.1_f32.powi(wheel);
+                        if !ndel.is_zero() && ndel.is_finite() {
+                            delay_time = ndel;
+                            dirty |= delay_enabled;
+                        }
+                    }
+                    if is_key_pressed(KeyCode::Up) && pade_order < 8 {
+                        pade_order += 1;
+                        dirty |= delay_enabled;
+                    }
+                    if is_key_pressed(KeyCode::Down) && pade_order > 1 {
+                        pade_order -= 1;
+                        dirty |= delay_enabled;
+                    }
+                }
+                Mode::SampleTime => {
+                    if wheel != 0 {
+                        let nts = sample_time * 1.1_f32.powi(wheel);
+                        if !nts.is_zero() && nts.is_finite() {
+                            sample_time = nts;
+                            if rl.get_domain().sample_time().is_some() {
+                                rl.set_domain(Domain::Discrete { sample_time });
+                            }
+                        }
+                    }
+                }
+                Mode::Gain => {
+                    if wheel != 0 {
+                        let ng = gain * 1.1_f32.powi(wheel);
+                        if !ng.is_zero() && ng.is_finite() {
+                            gain = ng;
                         }
                     }
                 }
@@ -170,6 +433,7 @@ pub async fn mainloop() {
             let comp_mouse = Complex::new(mx, my);
 
             if let Some(r) = dragging_root {
+                dirty = true;
                 let root = if r < a_roots.len() {
                     &mut a_roots[r]
                 } else {
@@ -225,6 +489,7 @@ pub async fn mainloop() {
                     }
 
                     dragging_root = Some(i);
+                    dirty = true;
                     break;
                 }
             }
@@ -258,42 +523,43 @@ pub async fn mainloop() {
 
         clear_background(BLACK);
 
-        if let Some(branch) = filter {
-            for roots in all_roots
-                .iter()
-                .skip(branch)
-                .step_by(rl.get_branches())
-                .collect::<Vec<_>>()
-                .windows(2)
-            {
-                let p = roots[1];
-                let q = roots[0];
+        for i in 0..rl.get_branches() {
+            if filter.is_some_and(|x| x != i) {
+                continue;
+            }
+
+            let branch = rl.iter_branch_sensitivity(i).collect::<Vec<_>>();
+            for points in branch.windows(2) {
+                let (p, q) = (points[0].root, points[1].root);
+                if !(p.re.is_finite() && p.im.is_finite() && q.re.is_finite() && q.im.is_finite()) {
+                    continue;
+                }
+
+                // Thicker where the roots move faster, relative to the gain
+                let thickness = if show_sensitivity {
+                    let rel = points[0].sensitivity.norm() * points[0].gain;
+                    if rel.is_finite() {
+                        (1.0 + 2.0 * rel.ln_1p()).min(8.0)
+                    } else {
+                        8.0
+                    }
+                } else {
+                    2.0
+                };
+
                 draw_line(
                     p.re * sx + ox,
                     p.im * sy + oy,
                     q.re * sx + ox,
                     q.im * sy + oy,
-                    2.0,
-                    COLORS[branch],
+                    thickness,
+                    COLORS[i % COLORS.len()],
                 );
             }
-        } else {
-            for roots in all_roots
-                .chunks(rl.get_branches())
-                .collect::<Vec<_>>()
-                .windows(2)
-            {
-                for (i, (p, q)) in roots[0].iter().zip(roots[1].iter()).enumerate() {
-                    draw_line(
-                        p.re * sx + ox,
-                        p.im * sy + oy,
-                        q.re * sx + ox,
-                        q.im * sy + oy,
-                        2.0,
-                        COLORS[i],
-                    );
-                }
-            }
+        }
+
+        if let Domain::Discrete { .. } = rl.get_domain() {
+            draw_ellipse_lines(ox, oy, sx.abs(), sy.abs(), 0.0, 1.0, DARKGRAY);
         }
 
         let radius = 2.0;
@@ -340,17 +606,67 @@ pub async fn mainloop() {
             }
         }
 
+        if show_exact {
+            for r in exact.iter().flat_map(|x| &x.roots) {
+                draw_circle(r.re * sx + ox, r.im * sy + oy, 1.5, SKYBLUE);
+            }
+        }
+
+        // Poles and zeros added by the delay approximation
+        if let Some(delay) = rl.get_delay() {
+            for p in delay.poles() {
+                draw_circle(p.re * sx + ox, p.im * sy + oy, radius, ORANGE);
+            }
+            for z in delay.zeros() {
+                draw_rectangle(
+                    z.re * sx + ox - radius,
+                    z.im * sy + oy - radius,
+                    2.0 * radius,
+                    2.0 * radius,
+                    ORANGE,
+                );
+            }
+        }
+
+        // Tangent markers at the complex poles and zeros
+        let marker = 15.0 / sx;
+        for ba in departure_angles(&a_roots, &b_roots)
+            .into_iter()
+            .chain(arrival_angles(&a_roots, &b_roots))
+        {
+            for &angle in &ba.angles {
+                let dir = Complex::from_polar(marker, angle);
+                for (c, d) in [(ba.root, dir), (ba.root.conj(), dir.conj())] {
+                    let e = c + d;
+                    draw_line(
+                        c.re * sx + ox,
+                        c.im * sy + oy,
+                        e.re * sx + ox,
+                        e.im * sy + oy,
+                        1.0,
+                        GRAY,
+                    );
+                }
+            }
+        }
+
+        if let Some(q) = gain_plot {
+            draw_gain_plot(
+                &rl,
+                GainPlotQuantity::ALL[q],
+                filter,
+                zeta,
+                options.get_gain_range(),
+            );
+        }
+
         let dur = t1.elapsed();
         tot += dur.as_nanos();
 
         match mode {
             Mode::Interval => {
                 draw_text(
-                    &format!(
-                        "Interval: {}\t\tPoints: {}",
-                        interval,
-                        all_roots.len() / rl.get_branches()
-                    ),
+                    &format!("Interval: {}\t\tPoints: {}", interval, rl.get_gains().len()),
                     5.0,
                     screen_height() - 15.0,
                     30.0,
@@ -366,7 +682,91 @@ pub async fn mainloop() {
                     WHITE,
                 );
             }
-            _ => {}
+            Mode::Damping => {
+                // Constant damping curve, mirrored on the lower half plane
+                let curve = match rl.get_domain() {
+                    Domain::Continuous => vec![
+                        Complex::zero(),
+                        Complex::new(-zeta, (1.0 - zeta * zeta).sqrt())
+                            * (screen_width() + screen_height())
+                            / sx.abs().min(sy.abs()),
+                    ],
+                    Domain::Discrete { .. } => damping_spiral(zeta, CURVE_POINTS),
+                };
+                for w in curve.windows(2) {
+                    for (p, q) in [(w[0], w[1]), (w[0].conj(), w[1].conj())] {
+                        draw_line(
+                            p.re * sx + ox,
+                            p.im * sy + oy,
+                            q.re * sx + ox,
+                            q.im * sy + oy,
+                            1.0,
+                            DARKGRAY,
+                        );
+                    }
+                }
+
+                // The wheel keeps zeta in [0, 1]
+                let points = rl.gains_for_damping(zeta).unwrap_or_default();
+                for p in &points {
+                    draw_circle_lines(p.point.re * sx + ox, p.point.im * sy + oy, 5.0, 1.0, WHITE);
+                }
+
+                let gains = points
+                    .iter()
+                    .map(|p| format!("{:.3}", p.gain))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                draw_text(
+                    &format!("Damping: {zeta:.2}\t\tGains: {gains}"),
+                    5.0,
+                    screen_height() - 15.0,
+                    30.0,
+                    WHITE,
+                );
+            }
+            Mode::Delay => {
+                let text = if delay_enabled {
+                    format!("Delay: {delay_time:.3} s\t\tPadé order: {pade_order}")
+                } else {
+                    format!("Delay: off ({delay_time:.3} s)\t\tPadé order: {pade_order}")
+                };
+                draw_text(&text, 5.0, screen_height() - 15.0, 30.0, WHITE);
+            }
+            Mode::SampleTime => {
+                let plane = if rl.get_domain().sample_time().is_some() {
+                    "z-plane"
+                } else {
+                    "s-plane"
+                };
+                draw_text(
+                    &format!("Sample time: {sample_time:.3} s ({plane})"),
+                    5.0,
+                    screen_height() - 15.0,
+                    30.0,
+                    WHITE,
+                );
+            }
+            Mode::Gain => {
+                // Closed-loop roots at the selected gain
+                for r in rl.roots_at_gain(gain) {
+                    draw_circle_lines(r.re * sx + ox, r.im * sy + oy, 5.0, 1.0, WHITE);
+                }
+
+                let state = if rl.is_stable_at(gain) {
+                    "stable"
+                } else {
+                    "unstable"
+                };
+                draw_text(
+                    &format!("Gain: {gain:.3} ({state})"),
+                    5.0,
+                    screen_height() - 15.0,
+                    30.0,
+                    WHITE,
+                );
+            }
+            Mode::Zoom => {}
         }
 
         if last_update_time.elapsed().as_millis() >= 500 {
@@ -379,6 +779,84 @@ pub async fn mainloop() {
         if fps {
             draw_text(&format!("FPS: {}", val_fps), 5.0, 20.0, 30.0, WHITE);
         }
+        if show_stability {
+            let stable = rl
+                .stability_crossings()
+                .stable_gains
+                .iter()
+                .map(|x| format!("[{:.3}, {:.3}]", x.min, x.max))
+                .collect::<Vec<_>>();
+            let text = if stable.is_empty() {
+                "Stable: none".to_owned()
+            } else {
+                format!("Stable: k in {}", stable.join(" U "))
+            };
+
+            // The lowest gains meeting the error bound, to hold against the stable range
+            let bounds = ERROR_INPUTS
+                .iter()
+                .map(|&(input, name)| {
+                    rl.min_gain_for_error(input, ERROR_BOUND).map_or_else(
+                        || format!("{name} never"),
+                        |k| format!("{name} k >= {k:.3}"),
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            draw_text(
+                &format!("{text}\t\tError <= {ERROR_BOUND}: {bounds}"),
+                5.0,
+                45.0,
+                30.0,
+                WHITE,
+            );
+
+            // Error constants per unit gain, the steady-state errors follow from k
+            let open_loop = rl.open_loop();
+            let constants = open_loop.error_constants();
+            draw_text(
+                &format!(
+                    "Type {}\t\tKp = {:.3} k\t\tKv = {:.3} k\t\tKa = {:.3} k",
+                    open_loop.system_type(),
+                    constants.position,
+                    constants.velocity,
+                    constants.acceleration
+                ),
+                5.0,
+                120.0,
+                30.0,
+                WHITE,
+            );
+
+            let errors = ERROR_INPUTS
+                .iter()
+                .map(|&(input, name)| format!("{name} {:.3}", rl.steady_state_error(gain, input)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            draw_text(
+                &format!("Errors at k = {gain:.3}: {errors}"),
+                5.0,
+                145.0,
+                30.0,
+                WHITE,
+            );
+        }
+        if let Some(delay) = rl.get_delay() {
+            draw_text(
+                &format!(
+                    "Delay: {:.3} s (Padé {})",
+                    delay.get_time(),
+                    delay.get_order()
+                ),
+                5.0,
+                70.0,
+                30.0,
+                ORANGE,
+            );
+        }
+        if let Some(e) = error {
+            draw_text(&format!("Error: {e}"), 5.0, 95.0, 30.0, RED);
+        }
         if show_load {
             draw_text(
                 &format!("Load: {}%", val_load),
@@ -1,4 +1,8 @@
-use std::{io::{stdout, Write}, ops::Range, time::Instant};
+use std::{
+    io::{stdout, Write},
+    ops::Range,
+    time::Instant,
+};
 
 use wgpu::{include_wgsl, util::DeviceExt};
 use winit::{
@@ -10,12 +14,18 @@ use winit::{
 
 const VSYNC: bool = false;
 
-#[repr(C)]
-#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct Vertex {
-    position: [f32; 2],
-    color: u32,
+mod vertex {
+    // The padding check generated by the `bytemuck::Pod` derive is never called
+    #![allow(dead_code)]
+
+    #[repr(C)]
+    #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+    pub struct Vertex {
+        pub position: [f32; 2],
+        pub color: u32,
+    }
 }
+use vertex::Vertex;
 
 impl Vertex {
     fn desc() -> wgpu::VertexBufferLayout<'static> {
@@ -286,11 +296,8 @@ impl<'a> State<'a> {
     }
 
     fn input(&mut self, event: &WindowEvent) -> bool {
-        match event {
-            WindowEvent::CursorMoved { position, .. } => {
-                self.app.mouse_pos = Some((position.x, position.y));
-            }
-            _ => {}
+        if let WindowEvent::CursorMoved { position, .. } = event {
+            self.app.mouse_pos = Some((position.x, position.y));
         }
 
         false
@@ -306,20 +313,27 @@ impl<'a> State<'a> {
 
         self.app.fps_lst.rotate_right(1);
         self.app.fps_lst[0] = fps;
-        self.app.fps_min = self.app.fps_lst.iter().copied().min_by(|a, b| 
-            match (a, b) {
+        self.app.fps_min = self
+            .app
+            .fps_lst
+            .iter()
+            .copied()
+            .min_by(|a, b| match (a, b) {
                 (x, _) if x.is_nan() => std::cmp::Ordering::Less,
                 (_, y) if y.is_nan() => std::cmp::Ordering::Greater,
                 _ => a.partial_cmp(b).unwrap(),
-            }
-        ).unwrap();
+            })
+            .unwrap();
         if self.app.fps.is_nan() {
             self.app.fps = fps;
         } else {
             self.app.fps = self.app.fps * 0.95 + fps * 0.05;
         }
 
-        print!("\rFramerate: {:.0} \t {:.0}        ", self.app.fps, self.app.fps_min);
+        print!(
+            "\rFramerate: {:.0} \t {:.0}        ",
+            self.app.fps, self.app.fps_min
+        );
         stdout().flush().unwrap();
 
         let dtheta = 60f32.to_radians() * dt;
@@ -427,15 +441,15 @@ pub async fn run() {
             },
             //
             Vertex {
-                position: [-0.0968241, 0.59240386],
+                position: [-0.0968241, 0.592_403_9],
                 color: 1,
             },
             Vertex {
-                position: [-0.59513406, 0.07958647],
+                position: [-0.595_134, 0.07958647],
                 color: 1,
             },
             Vertex {
-                position: [-0.31918549, -0.54939706],
+                position: [-0.319_185_5, -0.54939706],
                 color: 1,
             },
             Vertex {
@@ -6,7 +6,9 @@ use std::{
 };
 
 use self::roots::PolynomialRoot;
+use crate::error::{Error, Result};
 
+pub mod quasi;
 pub mod root_locus;
 pub mod roots;
 
@@ -19,7 +21,9 @@ pub type Polynomial64 = Polynomial<f64>;
 pub fn conv<N: Num + Copy>(a: &[N], b: &[N], out: &mut [N]) {
     debug_assert!(a.len() + b.len() - 1 <= out.len());
 
-    out.iter_mut().for_each(|x| *x = N::zero());
+    for x in out.iter_mut() {
+        *x = N::zero();
+    }
 
     for (i, &x) in a.iter().enumerate() {
         for (j, &y) in b.iter().enumerate() {
@@ -46,6 +50,23 @@ impl<F: Float> Polynomial<F> {
         Self(vec)
     }
 
+    /// Same as `Polynomial::new`, but checks the coefficients
+    ///
+    /// # Errors
+    ///
+    /// `Error::EmptyPolynomial` if `vec` is empty, and `Error::NonFiniteCoefficients`
+    /// if any coefficient is NaN or infinite
+    pub fn try_new(vec: Vec<F>) -> Result<Self> {
+        if vec.is_empty() {
+            return Err(Error::EmptyPolynomial);
+        }
+        if !vec.iter().all(|x| x.is_finite()) {
+            return Err(Error::NonFiniteCoefficients);
+        }
+
+        Ok(Self::new(vec))
+    }
+
     /// Calculates the polynomial with the given roots and gain
     ///
     /// P.S.: If complex roots are given, they must have their complex conjugate too, else the computation will fail
@@ -76,7 +97,7 @@ impl<F: Float> Polynomial<F> {
         let mut out_copy = out.clone();
         let mut i = 0;
 
-        for root in roots.iter() {
+        for root in roots {
             match *root {
                 PolynomialRoot::RealSingle(r) => {
                     conv(&out_copy[..=i], &[-r, F::one()], &mut out);
@@ -138,9 +159,21 @@ impl<F: Float> Polynomial<F> {
         &self.0
     }
 
+    /// Order of the polynomial, an empty polynomial has order 0
+    #[must_use]
+    pub const fn order(&self) -> usize {
+        self.0.len().saturating_sub(1)
+    }
+
+    /// Checks whether every coefficient is zero, which includes the empty polynomial
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(Zero::is_zero)
+    }
+
     #[must_use]
-    pub fn order(&self) -> usize {
-        self.0.len() - 1
+    pub fn is_finite(&self) -> bool {
+        self.0.iter().all(|x| x.is_finite())
     }
 
     pub fn eval(&self, x: F) -> F {
@@ -198,6 +231,64 @@ impl<F: Float> Polynomial<F> {
         )
     }
 
+    /// Splits the polynomial along the imaginary axis
+    ///
+    /// Returns `(R, I)`, both real polynomials in ω, such that `P(jω) = R(ω) + j I(ω)`
+    #[must_use]
+    pub fn imaginary_axis_parts(&self) -> (Self, Self) {
+        let mut re = vec![F::zero(); self.0.len()];
+        let mut im = vec![F::zero(); self.0.len()];
+
+        // jⁿ cycles through 1, j, -1, -j
+        for (i, &term) in self.0.iter().enumerate() {
+            match i % 4 {
+                0 => re[i] = term,
+                1 => im[i] = term,
+                2 => re[i] = -term,
+                _ => im[i] = -term,
+            }
+        }
+
+        (Self::new(re), Self::new(im))
+    }
+
+    /// Divides out the factor `(x - root)` as many times as `root` is a root
+    ///
+    /// `root` counts as a root while the value there is negligible next to the
+    /// sum of the magnitudes of the terms, so for `root = 0` only exact zeros do.
+    /// Returns the quotient and the multiplicity.
+    #[must_use]
+    pub fn deflate(&self, root: F) -> (Self, usize) {
+        let tol = F::epsilon().sqrt();
+
+        let mut poly = self.clone();
+        let mut count = 0;
+
+        while poly.order() > 0 {
+            let bound = poly
+                .0
+                .iter()
+                .rev()
+                .fold(F::zero(), |acc, &x| acc * root.abs() + x.abs());
+            if poly.eval(root).abs() > tol * bound {
+                break;
+            }
+
+            // Synthetic division, from the highest order down
+            let mut quotient = vec![F::zero(); poly.order()];
+            let mut carry = F::zero();
+            for i in (1..poly.0.len()).rev() {
+                carry = poly.0[i] + root * carry;
+                quotient[i - 1] = carry;
+            }
+
+            poly = Self::new(quotient);
+            count += 1;
+        }
+
+        (poly, count)
+    }
+
     // TODO: implement Polynomial methods
 }
 
@@ -205,7 +296,7 @@ impl<F: Float + Display> Display for Polynomial<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let terms = remove_trailing_zeros(self.get_terms());
         for (i, term) in terms.iter().enumerate().rev() {
-            write!(f, "{} x^{}", term, i)?;
+            write!(f, "{term} x^{i}")?;
             if i > 0 {
                 write!(f, " + ")?;
             }
@@ -289,10 +380,29 @@ impl<F: Float> Sub for Polynomial<F> {
 }
 
 #[cfg(test)]
+#[allow(clippy::many_single_char_names)]
 mod tests {
     use super::*;
     use approx::relative_eq;
 
+    #[test]
+    fn try_new() {
+        assert_eq!(
+            Polynomial::<f64>::try_new(vec![]),
+            Err(Error::EmptyPolynomial)
+        );
+        assert_eq!(
+            Polynomial::try_new(vec![1.0, f64::NAN]),
+            Err(Error::NonFiniteCoefficients)
+        );
+        assert_eq!(
+            Polynomial::try_new(vec![1.0, 2.0, 0.0]),
+            Ok(Polynomial(vec![1.0, 2.0]))
+        );
+
+        assert_eq!(Polynomial::<f64>::new(vec![]).order(), 0);
+    }
+
     #[test]
     fn from_roots() {
         let roots = vec![1.0, 2.0];
@@ -329,6 +439,30 @@ mod tests {
         assert_eq!(poly.derivative(), der);
     }
 
+    #[test]
+    fn imaginary_axis_parts() {
+        // 1 + 2s + 3s² + 4s³ at s = jω is (1 - 3ω²) + j (2ω - 4ω³)
+        let poly = Polynomial(vec![1.0, 2.0, 3.0, 4.0]);
+        let (re, im) = poly.imaginary_axis_parts();
+
+        assert_eq!(re, Polynomial(vec![1.0, 0.0, -3.0]));
+        assert_eq!(im, Polynomial(vec![0.0, 2.0, 0.0, -4.0]));
+    }
+
+    #[test]
+    fn deflate() {
+        // (x - 1)² (x + 2)
+        let poly = Polynomial::new(vec![2.0, -3.0, 0.0, 1.0]);
+
+        let (quotient, count) = poly.deflate(1.0);
+        assert_eq!(count, 2);
+        assert_eq!(quotient, Polynomial::new(vec![2.0, 1.0]));
+
+        let (quotient, count) = poly.deflate(0.0);
+        assert_eq!(count, 0);
+        assert_eq!(quotient, poly);
+    }
+
     #[test]
     fn add() {
         let a = Polynomial(vec![1.0, 2.0]); // 1 + 2x
@@ -0,0 +1,208 @@
+use num::{Complex, Float};
+
+use super::Polynomial;
+use crate::contour::{winding_number, Rectangle};
+
+/// Newton iterations tried on each isolated root
+const NEWTON_STEPS: usize = 50;
+/// Deepest subdivision of the search region
+const MAX_DEPTH: usize = 64;
+/// Where regions are cut, tried in order
+const SPLITS: [f64; 3] = [0.4913, 0.5377, 0.4471];
+
+/// The quasi-polynomial `A(s) + k B(s) e^(-sT)`, the characteristic function of a
+/// loop with a pure delay `T`
+///
+/// Unlike the Padé approximation it has infinitely many roots, so they can only
+/// be searched inside a bounded region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuasiPolynomial<F: Float> {
+    poly_a: Polynomial<F>,
+    poly_b: Polynomial<F>,
+    gain: F,
+    delay: F,
+}
+
+impl<F: Float> QuasiPolynomial<F> {
+    #[must_use]
+    pub const fn new(poly_a: Polynomial<F>, poly_b: Polynomial<F>, gain: F, delay: F) -> Self {
+        Self {
+            poly_a,
+            poly_b,
+            gain,
+            delay,
+        }
+    }
+
+    #[must_use]
+    pub const fn get_gain(&self) -> F {
+        self.gain
+    }
+
+    pub const fn set_gain(&mut self, gain: F) {
+        self.gain = gain;
+    }
+
+    #[must_use]
+    pub const fn get_delay(&self) -> F {
+        self.delay
+    }
+
+    #[must_use]
+    pub fn eval(&self, s: Complex<F>) -> Complex<F> {
+        let e = (-s * self.delay).exp();
+        self.poly_a.eval_complex(s) + self.poly_b.eval_complex(s) * e * self.gain
+    }
+
+    /// `A'(s) + k (B'(s) - T B(s)) e^(-sT)`
+    #[must_use]
+    pub fn eval_derivative(&self, s: Complex<F>) -> Complex<F> {
+        let e = (-s * self.delay).exp();
+        let b = self.poly_b.eval_complex_derivative(s) - self.poly_b.eval_complex(s) * self.delay;
+        self.poly_a.eval_complex_derivative(s) + b * e * self.gain
+    }
+
+    /// Finds every root inside `region`, with multiplicity, sorted by real part
+    ///
+    /// The region is split until the argument principle counts a single root
+    /// in each piece, which is then refined with Newton's method. Pieces are cut
+    /// slightly off-center, so that symmetric roots (e.g. on the real axis) do
+    /// not land on the cuts. A cluster that cannot be separated any further is
+    /// reported as a multiple root.
+    #[must_use]
+    pub fn roots_in(&self, region: &Rectangle<F>, prec: F) -> Vec<Complex<F>> {
+        let mut out = Vec::new();
+
+        // A root sits on the given boundary, grow the region a little
+        let mut region = *region;
+        let margin = region.width().max(region.height()) * F::from(1e-6).unwrap();
+        let n = self.count(&region).or_else(|| {
+            region = region.expand(margin);
+            self.count(&region)
+        });
+
+        if let Some(n) = n {
+            self.refine(&region, n, prec, 0, &mut out);
+        }
+
+        out.sort_by(|a, b| a.re.partial_cmp(&b.re).unwrap());
+        out
+    }
+
+    fn count(&self, region: &Rectangle<F>) -> Option<usize> {
+        let n = winding_number(|s| self.eval(s), &region.corners())?;
+        usize::try_from(n).ok()
+    }
+
+    fn refine(
+        &self,
+        region: &Rectangle<F>,
+        n: usize,
+        prec: F,
+        depth: usize,
+        out: &mut Vec<Complex<F>>,
+    ) {
+        if n == 0 {
+            return;
+        }
+
+        let size = region.width().max(region.height());
+        let tiny = size <= prec * (F::one() + region.center().norm()) || depth >= MAX_DEPTH;
+
+        if n == 1 || tiny {
+            let root = self.newton(region.center(), prec);
+            let margin = size / F::from(100).unwrap();
+
+            match root.filter(|&x| region.expand(margin).contains(x)) {
+                Some(x) => {
+                    out.extend(std::iter::repeat_n(x, n));
+                    return;
+                }
+                None if tiny => {
+                    out.extend(std::iter::repeat_n(region.center(), n));
+                    return;
+                }
+                None => {}
+            }
+        }
+
+        // Move the cut whenever it goes through a root
+        for ratio in SPLITS {
+            let (lo, hi) = region.split(F::from(ratio).unwrap());
+
+            if let (Some(a), Some(b)) = (self.count(&lo), self.count(&hi)) {
+                self.refine(&lo, a, prec, depth + 1, out);
+                self.refine(&hi, b, prec, depth + 1, out);
+                return;
+            }
+        }
+
+        out.extend(std::iter::repeat_n(region.center(), n));
+    }
+
+    fn newton(&self, mut s: Complex<F>, prec: F) -> Option<Complex<F>> {
+        for _ in 0..NEWTON_STEPS {
+            let step = self.eval(s) / self.eval_derivative(s);
+            if !step.re.is_finite() || !step.im.is_finite() {
+                return None;
+            }
+
+            s = s - step;
+
+            if step.norm() <= prec * (F::one() + s.norm()) {
+                return Some(s);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn no_delay_is_a_polynomial() {
+        // s² + 3s + 2 + k, with k = 2
+        let a = Polynomial::new(vec![2.0, 3.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+        let q = QuasiPolynomial::new(a, b, 2.0, 0.0);
+
+        let region = Rectangle::new(Complex::new(-5.0, -5.0), Complex::new(5.0, 5.0));
+        let roots = q.roots_in(&region, 1e-12);
+
+        // (s + 1.5)² + 1.75
+        assert_eq!(roots.len(), 2);
+        for r in roots {
+            assert_abs_diff_eq!(r.re, -1.5, epsilon = 1e-9);
+            assert_abs_diff_eq!(r.im.abs(), 1.75f64.sqrt(), epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn integrator_with_delay() {
+        // s + k e^(-s) has the roots s = W(-k), with k = π/2 the principal ones are ±jπ/2
+        // and the next ones have a real part of about -1.6
+        let a = Polynomial::new(vec![0.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+        let q = QuasiPolynomial::new(a, b, std::f64::consts::FRAC_PI_2, 1.0);
+
+        let region = Rectangle::new(Complex::new(-1.0, -10.0), Complex::new(1.0, 10.0));
+        let roots = q.roots_in(&region, 1e-12);
+
+        assert_eq!(roots.len(), 2);
+        for r in &roots {
+            assert_abs_diff_eq!(r.re, 0.0, epsilon = 1e-9);
+            assert_abs_diff_eq!(r.im.abs(), std::f64::consts::FRAC_PI_2, epsilon = 1e-9);
+        }
+
+        let region = Rectangle::new(Complex::new(-2.0, 5.0), Complex::new(1.0, 10.0));
+        let roots = q.roots_in(&region, 1e-12);
+
+        assert_eq!(roots.len(), 1);
+        assert_abs_diff_eq!(roots[0].re, -1.604_290_913_448_01, epsilon = 1e-9);
+        assert_abs_diff_eq!(roots[0].im, 7.647_192_276_124_59, epsilon = 1e-9);
+    }
+}
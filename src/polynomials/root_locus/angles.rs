@@ -0,0 +1,165 @@
+use num::{Complex, Float};
+
+use crate::polynomials::roots::PolynomialRoot;
+
+/// Angles of the root locus branches at a complex open-loop pole or zero
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchAngles<F> {
+    /// The root in the upper half plane, the angles at its conjugate are mirrored
+    pub root: Complex<F>,
+    /// How many times `root` appears in the list it was taken from
+    pub multiplicity: usize,
+    /// One angle per branch leaving/arriving at `root`, in radians within `(-π, π]`
+    pub angles: Vec<F>,
+}
+
+/// Computes the angles at which the branches leave each `ComplexPair` in `poles`
+///
+/// For a pole `p` of multiplicity `m`, the angle condition gives
+/// θ = (180° + 360° l + Σ ∠(p - zᵢ) - Σ ∠(p - pⱼ)) / m, for l = 0..m,
+/// where the second sum skips the copies of `p` itself
+#[must_use]
+pub fn departure_angles<F: Float>(
+    poles: &[PolynomialRoot<F>],
+    zeros: &[PolynomialRoot<F>],
+) -> Vec<BranchAngles<F>> {
+    branch_angles(poles, zeros)
+}
+
+/// Computes the angles at which the branches arrive at each `ComplexPair` in `zeros`
+///
+/// Same as `departure_angles` with the roles of poles and zeros swapped:
+/// θ = (180° + 360° l + Σ ∠(z - pⱼ) - Σ ∠(z - zᵢ)) / m
+#[must_use]
+pub fn arrival_angles<F: Float>(
+    poles: &[PolynomialRoot<F>],
+    zeros: &[PolynomialRoot<F>],
+) -> Vec<BranchAngles<F>> {
+    branch_angles(zeros, poles)
+}
+
+/// Lists every root, including the conjugate of the complex pairs
+fn expand<F: Float>(roots: &[PolynomialRoot<F>]) -> Vec<Complex<F>> {
+    let mut out = Vec::with_capacity(roots.len() * 2);
+
+    for root in roots {
+        match *root {
+            PolynomialRoot::RealSingle(r) => out.push(Complex::from(r)),
+            PolynomialRoot::ComplexPair(c) => {
+                out.push(c);
+                out.push(c.conj());
+            }
+        }
+    }
+
+    out
+}
+
+fn wrap_angle<F: Float>(angle: F) -> F {
+    let pi = F::from(std::f64::consts::PI).unwrap();
+    let tau = pi + pi;
+
+    let wrapped = angle - tau * (angle / tau).round();
+    if wrapped <= -pi {
+        wrapped + tau
+    } else {
+        wrapped
+    }
+}
+
+/// Angles at the complex roots of `own`, given the roots of the other polynomial
+fn branch_angles<F: Float>(
+    own: &[PolynomialRoot<F>],
+    other: &[PolynomialRoot<F>],
+) -> Vec<BranchAngles<F>> {
+    let pi = F::from(std::f64::consts::PI).unwrap();
+    let tol = F::epsilon().sqrt();
+
+    let own_all = expand(own);
+    let other_all = expand(other);
+
+    let mut out: Vec<BranchAngles<F>> = Vec::new();
+
+    for root in own {
+        let PolynomialRoot::ComplexPair(c) = *root else {
+            continue;
+        };
+        let r = Complex::new(c.re, c.im.abs());
+        let same = |x: &Complex<F>| (x - r).norm() <= tol * (F::one() + r.norm());
+
+        if out.iter().any(|x| same(&x.root)) {
+            continue;
+        }
+
+        let multiplicity = own_all.iter().filter(|x| same(x)).count();
+
+        let mut sum = pi;
+        for x in &other_all {
+            sum = sum + (r - x).arg();
+        }
+        for x in own_all.iter().filter(|x| !same(x)) {
+            sum = sum - (r - x).arg();
+        }
+
+        let m = F::from(multiplicity).unwrap();
+        let angles = (0..multiplicity)
+            .map(|l| wrap_angle((sum + F::from(2 * l).unwrap() * pi) / m))
+            .collect();
+
+        out.push(BranchAngles {
+            root: r,
+            multiplicity,
+            angles,
+        });
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn departure_single_pair() {
+        // (s + 2) / (s² + 2s + 2)
+        let poles = [PolynomialRoot::ComplexPair(Complex::new(-1.0, 1.0))];
+        let zeros = [PolynomialRoot::RealSingle(-2.0)];
+
+        let dep = departure_angles(&poles, &zeros);
+
+        assert_eq!(dep.len(), 1);
+        assert_eq!(dep[0].multiplicity, 1);
+        assert_abs_diff_eq!(dep[0].angles[0], 135f64.to_radians(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn arrival_single_pair() {
+        // (s² + 2s + 2) / (s (s + 2))
+        let poles = [
+            PolynomialRoot::RealSingle(0.0),
+            PolynomialRoot::RealSingle(-2.0),
+        ];
+        let zeros = [PolynomialRoot::ComplexPair(Complex::new(-1.0, 1.0))];
+
+        let arr = arrival_angles(&poles, &zeros);
+
+        // 180° + ∠(-1 + j) + ∠(1 + j) - ∠(2j) = 180° + 135° + 45° - 90° = -90°
+        assert_eq!(arr.len(), 1);
+        assert_abs_diff_eq!(arr[0].angles[0], -90f64.to_radians(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn departure_repeated_pair() {
+        let pair = PolynomialRoot::ComplexPair(Complex::new(-1.0, 1.0));
+        let poles = [pair, pair];
+
+        let dep = departure_angles(&poles, &[]);
+
+        assert_eq!(dep.len(), 1);
+        assert_eq!(dep[0].multiplicity, 2);
+        assert_abs_diff_eq!(dep[0].angles[0], 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(dep[0].angles[1], std::f64::consts::PI, epsilon = 1e-12);
+    }
+}
@@ -0,0 +1,257 @@
+use std::fmt::{Debug, Display};
+
+use num::{Complex, Float, One, Zero};
+use rand::{distributions::Standard, prelude::Distribution};
+
+use super::{design::DesignPoint, RootLocus};
+use crate::polynomials::{conv, roots::RootFinding, Polynomial};
+
+/// A curve of the s-plane that can be intersected with the root locus
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Curve<F> {
+    /// The line `Re(s) = x`, e.g. `x = -σ` for a settling time bound
+    VerticalLine(F),
+    /// The line `Im(s) = y`
+    HorizontalLine(F),
+    /// The half line `origin + t direction`, for `t >= 0`
+    Ray {
+        origin: Complex<F>,
+        direction: Complex<F>,
+    },
+    /// The circle `|s - center| = radius`
+    Circle { center: Complex<F>, radius: F },
+    /// Straight segments joining consecutive vertices
+    Polyline(Vec<Complex<F>>),
+}
+
+/// A rational parametrization `s(t) = N(t) / D(t)` of part of a curve, for `t` in `range`
+///
+/// `D` must be real and positive over the range
+struct Piece<F> {
+    num: Vec<Complex<F>>,
+    den: Vec<Complex<F>>,
+    range: (F, F),
+}
+
+impl<F: Float> Curve<F> {
+    fn pieces(&self) -> Vec<Piece<F>> {
+        let line = |num: Vec<Complex<F>>, range| Piece {
+            num,
+            den: vec![Complex::one()],
+            range,
+        };
+        let all = (F::neg_infinity(), F::infinity());
+
+        match self {
+            Self::VerticalLine(x) => {
+                vec![line(vec![Complex::from(*x), Complex::i()], all)]
+            }
+            Self::HorizontalLine(y) => {
+                vec![line(vec![Complex::new(F::zero(), *y), Complex::one()], all)]
+            }
+            Self::Ray { origin, direction } => {
+                vec![line(vec![*origin, *direction], (F::zero(), F::infinity()))]
+            }
+            Self::Circle { center, radius } => {
+                // s = c + r ((1 - t²) + 2 j t) / (1 + t²)
+                let r = *radius;
+                vec![Piece {
+                    num: vec![center + r, Complex::new(F::zero(), r + r), center - r],
+                    den: vec![Complex::one(), Complex::zero(), Complex::one()],
+                    range: all,
+                }]
+            }
+            Self::Polyline(vertices) => vertices
+                .windows(2)
+                .map(|w| line(vec![w[0], w[1] - w[0]], (F::zero(), F::one())))
+                .collect(),
+        }
+    }
+
+    /// Points of the curve that no piece reaches for a finite parameter
+    fn extra_points(&self) -> Vec<Complex<F>> {
+        match self {
+            Self::Circle { center, radius } => vec![center - radius],
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn mul_complex<F: Float>(a: &[Complex<F>], b: &[Complex<F>]) -> Vec<Complex<F>> {
+    let mut out = vec![Complex::zero(); a.len() + b.len() - 1];
+    conv(a, b, &mut out);
+    out
+}
+
+fn eval_complex<F: Float>(p: &[Complex<F>], t: F) -> Complex<F> {
+    p.iter().rev().fold(Complex::zero(), |acc, &x| acc * t + x)
+}
+
+/// Computes `Σ pᵢ N(t)ⁱ D(t)ⁿ⁻ⁱ`, that is `P(N / D) Dⁿ`
+fn compose<F: Float>(
+    poly: &Polynomial<F>,
+    num: &[Complex<F>],
+    den: &[Complex<F>],
+    n: usize,
+) -> Vec<Complex<F>> {
+    let terms = poly.get_terms();
+
+    // Powers of D, from D⁰ to Dⁿ
+    let mut den_pows = vec![vec![Complex::one()]];
+    for i in 0..n {
+        den_pows.push(mul_complex(&den_pows[i], den));
+    }
+
+    let mut out = vec![Complex::zero()];
+    let mut num_pow = vec![Complex::one()];
+
+    for (i, &p) in terms.iter().enumerate() {
+        let term = mul_complex(&num_pow, &den_pows[n - i]);
+
+        if out.len() < term.len() {
+            out.resize(term.len(), Complex::zero());
+        }
+        for (o, t) in out.iter_mut().zip(term) {
+            *o = *o + t * p;
+        }
+
+        num_pow = mul_complex(&num_pow, num);
+    }
+
+    out
+}
+
+impl<F: Float + Display + Debug> RootLocus<F>
+where
+    Standard: Distribution<F>,
+{
+    /// Finds every point where the branches cross `curve`, sorted by gain
+    ///
+    /// Each piece of the curve is written as `s(t) = N(t) / D(t)`. Substituting
+    /// into `A + k B = 0` and multiplying by `Dⁿ` gives `Â(t) + k B̂(t) = 0`,
+    /// which only has a real `k` where `Im(Â conj(B̂)) = 0`. The gains are then
+    /// exact instead of interpolated from the stored samples.
+    #[must_use]
+    pub fn intersections(&self, curve: &Curve<F>) -> Vec<DesignPoint<F>> {
+        let tol = F::epsilon().sqrt();
+
+        let mut points: Vec<(Complex<F>, F)> = Vec::new();
+
+        let found = curve
+            .pieces()
+            .iter()
+            .flat_map(|x| self.intersect_piece(x))
+            .chain(
+                curve
+                    .extra_points()
+                    .into_iter()
+                    .filter_map(|x| self.locus_gain(x).map(|gain| (x, gain))),
+            )
+            .collect::<Vec<_>>();
+
+        // Consecutive polyline segments share their vertices
+        for (point, gain) in found {
+            if !points
+                .iter()
+                .any(|(x, _)| (x - point).norm() <= tol * (F::one() + point.norm()))
+            {
+                points.push((point, gain));
+            }
+        }
+
+        let mut out = points
+            .into_iter()
+            .map(|(point, gain)| DesignPoint {
+                gain,
+                point,
+                roots: self.roots_at_gain(gain),
+            })
+            .collect::<Vec<_>>();
+
+        out.sort_by(|a, b| a.gain.partial_cmp(&b.gain).unwrap());
+        out
+    }
+
+    fn intersect_piece(&self, piece: &Piece<F>) -> Vec<(Complex<F>, F)> {
+        let tol = F::epsilon().sqrt();
+        let n = self.poly_a.order().max(self.poly_b.order());
+
+        let a = compose(&self.poly_a, &piece.num, &piece.den, n);
+        let b = compose(&self.poly_b, &piece.num, &piece.den, n)
+            .into_iter()
+            .map(|x| x.conj())
+            .collect::<Vec<_>>();
+
+        let cross = Polynomial::new(mul_complex(&a, &b).into_iter().map(|x| x.im).collect());
+
+        // `k` is real all along the curve (e.g. the real axis), there are no isolated crossings
+        if cross.get_terms().iter().all(Zero::is_zero) {
+            return Vec::new();
+        }
+
+        let (min, max) = piece.range;
+
+        cross
+            .find_real_roots(F::epsilon())
+            .into_iter()
+            .filter(|&t| t >= min - tol && t <= max + tol)
+            .filter_map(|t| {
+                let point = eval_complex(&piece.num, t) / eval_complex(&piece.den, t);
+                self.locus_gain(point).map(|gain| (point, gain))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn second_order() -> RootLocus<f64> {
+        // 1 / (s (s + 2)), the locus is [-2, 0] and the line Re(s) = -1
+        let a = Polynomial::new(vec![0.0, 2.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+
+        RootLocus::new(a, b).unwrap()
+    }
+
+    #[test]
+    fn vertical_line() {
+        let res = second_order().intersections(&Curve::VerticalLine(-0.5));
+
+        assert_eq!(res.len(), 1);
+        assert_abs_diff_eq!(res[0].point.re, -0.5, epsilon = 1e-9);
+        assert_abs_diff_eq!(res[0].gain, 0.75, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn circle() {
+        let res = second_order().intersections(&Curve::Circle {
+            center: Complex::new(-1.0, 0.0),
+            radius: 1.0,
+        });
+
+        // Both open-loop poles and the pair -1 ± j
+        let gains = res.iter().map(|x| x.gain).collect::<Vec<_>>();
+        assert_eq!(gains.len(), 4);
+        assert_abs_diff_eq!(gains[0], 0.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(gains[1], 0.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(gains[2], 2.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(gains[3], 2.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn polyline() {
+        let res = second_order().intersections(&Curve::Polyline(vec![
+            Complex::new(-3.0, 1.0),
+            Complex::new(1.0, 1.0),
+            Complex::new(1.0, -1.0),
+        ]));
+
+        assert_eq!(res.len(), 1);
+        assert_abs_diff_eq!(res[0].point.re, -1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(res[0].point.im, 1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(res[0].gain, 2.0, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt::{Debug, Display};
+
+use num::{Complex, Float, Zero};
+use rand::{distributions::Standard, prelude::Distribution};
+
+use super::RootLocus;
+use crate::{
+    contour::Rectangle,
+    error::{Error, Result},
+    polynomials::{quasi::QuasiPolynomial, roots::RootFinding, Polynomial},
+};
+
+/// Padé approximant of a pure time delay, `e^(-sT) ≈ Q(-s) / Q(s)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PadeDelay<F: Float> {
+    time: F,
+    order: usize,
+    numerator: Polynomial<F>,
+    denominator: Polynomial<F>,
+}
+
+impl<F: Float + Debug> PadeDelay<F> {
+    /// Builds the `(order, order)` Padé approximant of a delay of `time` seconds
+    ///
+    /// `Q(s) = Σ cₖ (sT)ᵏ`, with `cₖ = (2n - k)! n! / ((2n)! k! (n - k)!)`
+    ///
+    /// # Errors
+    ///
+    /// `Error::InvalidParameter` if the order is zero or the delay is not positive and finite
+    pub fn new(time: F, order: usize) -> Result<Self> {
+        if order == 0 {
+            return Err(Error::InvalidParameter(
+                "the Padé approximant needs at least one pole",
+            ));
+        }
+        if !time.is_finite() || time <= F::zero() {
+            return Err(Error::InvalidParameter(
+                "the delay must be positive and finite",
+            ));
+        }
+
+        let mut den = Vec::with_capacity(order + 1);
+        let mut c = F::one();
+        let mut t = F::one();
+
+        for k in 0..=order {
+            den.push(c * t);
+
+            let kf = F::from(k).unwrap();
+            let n = F::from(order).unwrap();
+            c = c * (n - kf) / ((n + n - kf) * (kf + F::one()));
+            t = t * time;
+        }
+
+        // Q(-s) only flips the sign of the odd powers
+        let num = den
+            .iter()
+            .enumerate()
+            .map(|(k, &x)| if k % 2 == 0 { x } else { -x })
+            .collect();
+
+        Ok(Self {
+            time,
+            order,
+            numerator: Polynomial::new(num),
+            denominator: Polynomial::new(den),
+        })
+    }
+
+    #[must_use]
+    pub const fn get_time(&self) -> F {
+        self.time
+    }
+
+    #[must_use]
+    pub const fn get_order(&self) -> usize {
+        self.order
+    }
+
+    /// `Q(-s)`, whose roots are the extra zeros added by the approximation
+    #[must_use]
+    pub const fn get_numerator(&self) -> &Polynomial<F> {
+        &self.numerator
+    }
+
+    /// `Q(s)`, whose roots are the extra poles added by the approximation
+    #[must_use]
+    pub const fn get_denominator(&self) -> &Polynomial<F> {
+        &self.denominator
+    }
+
+    /// The extra poles, all in the left half plane
+    #[must_use]
+    pub fn poles(&self) -> Vec<Complex<F>> {
+        let mut out = vec![Complex::zero(); self.order];
+        self.denominator.find_roots(&mut out, F::epsilon());
+        out
+    }
+
+    /// The extra zeros, mirror images of the poles across the imaginary axis
+    #[must_use]
+    pub fn zeros(&self) -> Vec<Complex<F>> {
+        self.poles().into_iter().map(|x| -x.conj()).collect()
+    }
+
+    /// Evaluates the approximant at a complex point
+    #[must_use]
+    pub fn eval(&self, s: Complex<F>) -> Complex<F> {
+        self.numerator.eval_complex(s) / self.denominator.eval_complex(s)
+    }
+}
+
+/// The exact roots of `A + k B e^(-sT)` found inside a region for one gain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelaySample<F> {
+    pub gain: F,
+    pub roots: Vec<Complex<F>>,
+}
+
+impl<F: Float + Display + Debug> RootLocus<F>
+where
+    Standard: Distribution<F>,
+{
+    /// Adds a pure delay to the open loop, `G(s) e^(-sT)`, replaced by its Padé approximant
+    #[must_use]
+    pub fn with_delay(mut self, delay: PadeDelay<F>) -> Self {
+        self.set_delay(Some(delay));
+        self
+    }
+
+    /// Sets or removes the open-loop delay
+    ///
+    /// The approximant is folded into the characteristic pair, so `A` becomes
+    /// `A Q(s)` and `B` becomes `B Q(-s)`, and the locus gains `order` branches
+    pub fn set_delay(&mut self, delay: Option<PadeDelay<F>>) {
+        self.delay = delay;
+        self.refresh_open_loop();
+    }
+
+    #[must_use]
+    pub const fn get_delay(&self) -> Option<&PadeDelay<F>> {
+        self.delay.as_ref()
+    }
+
+    /// Traces the true roots of the delayed loop inside `region`, one sample per gain
+    ///
+    /// This uses the exact `e^(-sT)` of the delay set with `set_delay` instead of
+    /// its Padé approximant (no delay at all when none is set), so both loci can
+    /// be compared. Roots may enter or leave the region, so the samples do not
+    /// all have the same number of roots.
+    #[must_use]
+    pub fn exact_delay_locus(&self, region: &Rectangle<F>, gains: &[F]) -> Vec<DelaySample<F>> {
+        let delay = self
+            .delay
+            .as_ref()
+            .map_or_else(F::zero, PadeDelay::get_time);
+        let mut quasi =
+            QuasiPolynomial::new(self.plant_a.clone(), self.plant_b.clone(), F::zero(), delay);
+
+        gains
+            .iter()
+            .map(|&gain| {
+                quasi.set_gain(gain);
+                DelaySample {
+                    gain,
+                    roots: quasi.roots_in(region, self.prec),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn first_order() {
+        let delay = PadeDelay::new(2.0, 1).unwrap();
+
+        // (1 - s) / (1 + s)
+        assert_eq!(delay.get_denominator(), &Polynomial::new(vec![1.0, 1.0]));
+        assert_eq!(delay.get_numerator(), &Polynomial::new(vec![1.0, -1.0]));
+
+        assert!(matches!(
+            PadeDelay::new(2.0, 0),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            PadeDelay::new(-1.0, 1),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn approximates_delay() {
+        let delay = PadeDelay::new(0.5, 4).unwrap();
+
+        for omega in [0.1, 1.0, 2.0] {
+            let s = Complex::new(0.0, omega);
+            let exact = (-s * 0.5).exp();
+            assert!((delay.eval(s) - exact).norm() < 1e-6);
+        }
+
+        for (p, z) in delay.poles().iter().zip(delay.zeros()) {
+            assert!(p.re < 0.0);
+            assert_abs_diff_eq!(z.re, -p.re, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn locus_gains_branches() {
+        // 1 / (s + 1) with a delay of 1s
+        let a = Polynomial::new(vec![1.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+
+        let rl = RootLocus::new(a, b)
+            .unwrap()
+            .with_delay(PadeDelay::new(1.0, 3).unwrap());
+
+        assert_eq!(rl.get_branches(), 4);
+        assert_eq!(rl.get_delay().unwrap().get_order(), 3);
+    }
+
+    #[test]
+    fn pade_matches_exact_roots() {
+        // 1 / (s + 1) with a delay of 0.2s, the dominant roots barely move with the approximation
+        let a = Polynomial::new(vec![1.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+
+        let rl = RootLocus::new(a, b)
+            .unwrap()
+            .with_delay(PadeDelay::new(0.2, 4).unwrap());
+        let region = Rectangle::new(Complex::new(-5.0, -8.0), Complex::new(1.0, 8.0));
+
+        let samples = rl.exact_delay_locus(&region, &[0.5, 2.0, 5.0]);
+
+        // A real root, then none (the complex pair is still left of the region), then the pair
+        let counts = samples.iter().map(|x| x.roots.len()).collect::<Vec<_>>();
+        assert_eq!(counts, [1, 0, 2]);
+
+        for sample in samples {
+            let pade = rl.roots_at_gain(sample.gain);
+            for r in sample.roots {
+                assert!(pade.iter().any(|x| (x - r).norm() < 1e-3));
+            }
+        }
+    }
+
+    #[test]
+    fn exact_root_on_the_boundary() {
+        // s + k e^(-s) with k = π/2 has a root at jπ/2, on the top edge of the region
+        // and between two of its samples, where the contour refinement runs out of depth
+        let a = Polynomial::new(vec![0.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+
+        let rl = RootLocus::new(a, b)
+            .unwrap()
+            .with_delay(PadeDelay::new(1.0, 2).unwrap());
+        let half_pi = std::f64::consts::FRAC_PI_2;
+        let region = Rectangle::new(Complex::new(-1.0, -1.0), Complex::new(0.7, half_pi));
+
+        let samples = rl.exact_delay_locus(&region, &[half_pi]);
+
+        assert_eq!(samples[0].roots.len(), 1);
+        assert_abs_diff_eq!(samples[0].roots[0].re, 0.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(samples[0].roots[0].im, half_pi, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,283 @@
+use std::fmt::{Debug, Display};
+
+use num::{Complex, Float};
+use rand::{distributions::Standard, prelude::Distribution};
+
+use super::{
+    curves::Curve,
+    discrete::{
+        damping_spiral, damping_spiral_point, natural_frequency_contour, natural_frequency_point,
+        CURVE_POINTS,
+    },
+    Domain, RootLocus,
+};
+use crate::error::{Error, Result};
+
+/// A point where the root locus meets a design target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesignPoint<F> {
+    /// Gain that places a closed-loop root at `point`
+    pub gain: F,
+    /// The intersection itself
+    pub point: Complex<F>,
+    /// Every closed-loop root at `gain`
+    pub roots: Vec<Complex<F>>,
+}
+
+impl<F: Float + Display + Debug> RootLocus<F>
+where
+    Standard: Distribution<F>,
+{
+    /// Finds where the locus crosses the curve of constant damping ratio `zeta`
+    ///
+    /// In the s-plane it is the ray leaving the origin at an angle of `180° - acos(ζ)`,
+    /// in the z-plane it is the spiral given by `discrete::damping_spiral`, whose
+    /// intersections are refined from its polyline approximation.
+    /// Only the upper half plane is searched since the locus is symmetric
+    ///
+    /// # Errors
+    ///
+    /// `Error::InvalidParameter` if `zeta` is not in `[0, 1]`
+    pub fn gains_for_damping(&self, zeta: F) -> Result<Vec<DesignPoint<F>>> {
+        if !(F::zero()..=F::one()).contains(&zeta) {
+            return Err(Error::InvalidParameter("damping ratio must lie in [0, 1]"));
+        }
+
+        let tol = F::epsilon().sqrt();
+
+        let mut points = match self.domain {
+            Domain::Continuous => self.intersections(&Curve::Ray {
+                origin: Complex::from(F::zero()),
+                direction: Complex::new(-zeta, (F::one() - zeta * zeta).sqrt()),
+            }),
+            // The spiral degenerates into a segment, which the polyline already is
+            Domain::Discrete { .. } if zeta >= F::one() => {
+                self.intersections(&Curve::Polyline(damping_spiral(zeta, CURVE_POINTS)))
+            }
+            Domain::Discrete { .. } => self.refined_intersections(
+                &damping_spiral(zeta, CURVE_POINTS),
+                (F::zero(), F::from(std::f64::consts::PI).unwrap()),
+                |theta| damping_spiral_point(zeta, theta),
+            ),
+        };
+        let origin = match self.domain {
+            Domain::Continuous => F::zero(),
+            Domain::Discrete { .. } => F::one(),
+        };
+
+        // The damping ratio is undefined at the origin itself
+        points.retain(|x| (x.point - origin).norm() > tol);
+        Ok(points)
+    }
+
+    /// Finds where the locus crosses the curve of constant natural frequency `omega_n`
+    ///
+    /// In the s-plane it is the circle `|s| = ωn`, in the z-plane it is the curve given by
+    /// `discrete::natural_frequency_contour`, whose intersections are refined from its
+    /// polyline approximation. Only the upper half plane is searched since the locus
+    /// is symmetric
+    ///
+    /// # Errors
+    ///
+    /// `Error::InvalidParameter` if `omega_n` is not positive and finite
+    pub fn gains_for_natural_frequency(&self, omega_n: F) -> Result<Vec<DesignPoint<F>>> {
+        if !omega_n.is_finite() || omega_n <= F::zero() {
+            return Err(Error::InvalidParameter(
+                "natural frequency must be positive and finite",
+            ));
+        }
+
+        let tol = F::epsilon().sqrt();
+
+        let mut points = match self.domain {
+            Domain::Continuous => self.intersections(&Curve::Circle {
+                center: Complex::from(F::zero()),
+                radius: omega_n,
+            }),
+            Domain::Discrete { sample_time } => {
+                let half_pi = F::from(std::f64::consts::FRAC_PI_2).unwrap();
+                self.refined_intersections(
+                    &natural_frequency_contour(omega_n, sample_time, CURVE_POINTS),
+                    (half_pi, half_pi + half_pi),
+                    |phi| natural_frequency_point(omega_n, sample_time, phi),
+                )
+            }
+        };
+
+        points.retain(|x| x.point.im >= -tol * omega_n);
+        Ok(points)
+    }
+
+    /// Intersects the locus with the polyline joining `curve(t)` at evenly spaced
+    /// parameters over `range`, then moves every intersection onto the curve itself
+    ///
+    /// The gain is real where the curve meets the locus, so the secant method is
+    /// applied to `Im(k(curve(t)))` from the parameter of the approximate point.
+    /// Points that do not converge close to it are kept as they are.
+    fn refined_intersections(
+        &self,
+        vertices: &[Complex<F>],
+        (start, end): (F, F),
+        curve: impl Fn(F) -> Complex<F>,
+    ) -> Vec<DesignPoint<F>> {
+        let step = (end - start) / F::from(vertices.len() - 1).unwrap();
+
+        let mut out = self
+            .intersections(&Curve::Polyline(vertices.to_vec()))
+            .into_iter()
+            .map(|approx| {
+                let t0 = start + step * polyline_parameter(vertices, approx.point);
+                self.refine_on_curve(&curve, t0, step).unwrap_or(approx)
+            })
+            .collect::<Vec<_>>();
+
+        out.sort_by(|a, b| a.gain.partial_cmp(&b.gain).unwrap());
+        out
+    }
+
+    fn refine_on_curve(
+        &self,
+        curve: impl Fn(F) -> Complex<F>,
+        t0: F,
+        step: F,
+    ) -> Option<DesignPoint<F>> {
+        let residual = |t: F| self.compute_gain(curve(t)).im;
+
+        let (mut a, mut b) = (t0, t0 + step / F::from(16).unwrap());
+        let (mut fa, mut fb) = (residual(a), residual(b));
+
+        for _ in 0..SECANT_STEPS {
+            if fb.is_zero() || fa == fb {
+                break;
+            }
+            let next = b - fb * (b - a) / (fb - fa);
+            if !next.is_finite() {
+                return None;
+            }
+            (a, fa) = (b, fb);
+            (b, fb) = (next, residual(next));
+            if (b - a).abs() <= F::epsilon() * (F::one() + b.abs()) {
+                break;
+            }
+        }
+
+        if (b - t0).abs() > step + step {
+            return None;
+        }
+
+        let point = curve(b);
+        self.locus_gain(point).map(|gain| DesignPoint {
+            gain,
+            point,
+            roots: self.roots_at_gain(gain),
+        })
+    }
+}
+
+/// Maximum number of secant steps used to refine an intersection
+const SECANT_STEPS: usize = 32;
+
+/// Position of `point` along the polyline, in units of segments from the first vertex
+fn polyline_parameter<F: Float>(vertices: &[Complex<F>], point: Complex<F>) -> F {
+    vertices
+        .windows(2)
+        .enumerate()
+        .map(|(i, w)| {
+            let edge = w[1] - w[0];
+            let t = ((point - w[0]) * edge.conj()).re / edge.norm_sqr();
+            let t = if t.is_finite() {
+                t.max(F::zero()).min(F::one())
+            } else {
+                F::zero()
+            };
+            let distance = (w[0] + edge * t - point).norm();
+            (F::from(i).unwrap() + t, distance)
+        })
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        .map_or_else(F::zero, |x| x.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::Polynomial;
+    use approx::assert_abs_diff_eq;
+
+    fn second_order() -> RootLocus<f64> {
+        // 1 / (s (s + 2)), closed loop s² + 2s + k has ωn = √k and ζ = 1 / √k
+        let a = Polynomial::new(vec![0.0, 2.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+
+        RootLocus::new(a, b).unwrap()
+    }
+
+    #[test]
+    fn damping_ratio() {
+        let rl = second_order();
+        let res = rl.gains_for_damping(0.5).unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert_abs_diff_eq!(res[0].gain, 4.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(res[0].point.re, -1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(res[0].point.im, 3f64.sqrt(), epsilon = 1e-9);
+        assert!(res[0]
+            .roots
+            .iter()
+            .any(|x| (x - res[0].point).norm() < 1e-6));
+
+        assert!(matches!(
+            rl.gains_for_damping(1.5),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn natural_frequency() {
+        let rl = second_order();
+
+        // The open-loop pole at -2 lies on the circle as well
+        let res = rl.gains_for_natural_frequency(2.0).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_abs_diff_eq!(res[0].gain, 0.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(res[1].gain, 4.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(res[1].point.im, 3f64.sqrt(), epsilon = 1e-9);
+
+        // Both roots are real, the one at -ωn is only reached as t → ∞
+        let res = rl.gains_for_natural_frequency(0.5).unwrap();
+        assert_eq!(res.len(), 1);
+        assert_abs_diff_eq!(res[0].gain, 0.75, epsilon = 1e-9);
+        assert_abs_diff_eq!(res[0].point.re, -0.5, epsilon = 1e-9);
+
+        assert!(matches!(
+            rl.gains_for_natural_frequency(0.0),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn discrete_targets_are_exact() {
+        // 1 / z², the closed-loop roots are ±j√k
+        let mut rl = RootLocus::new(
+            Polynomial::new(vec![0.0, 0.0, 1.0]),
+            Polynomial::new(vec![1.0]),
+        )
+        .unwrap();
+        rl.set_domain(Domain::Discrete { sample_time: 1.0 });
+
+        // The spiral meets the imaginary axis at |z| = e^(-ζ π / (2 √(1 - ζ²)))
+        let zeta = 0.5f64;
+        let res = rl.gains_for_damping(zeta).unwrap();
+        let expected = (-zeta * std::f64::consts::PI / zeta.mul_add(-zeta, 1.0).sqrt()).exp();
+        assert_eq!(res.len(), 1);
+        assert_abs_diff_eq!(res[0].gain, expected, epsilon = 1e-9);
+        assert_abs_diff_eq!(res[0].point.re, 0.0, epsilon = 1e-9);
+
+        // On the imaginary axis, ln|z|² + (π / 2)² = (ωn T)²
+        let half_pi = std::f64::consts::FRAC_PI_2;
+        let res = rl.gains_for_natural_frequency(2.0).unwrap();
+        let expected = (-2.0 * half_pi.mul_add(-half_pi, 4.0).sqrt()).exp();
+        assert_eq!(res.len(), 1);
+        assert_abs_diff_eq!(res[0].gain, expected, epsilon = 1e-9);
+        assert_abs_diff_eq!(res[0].point.re, 0.0, epsilon = 1e-9);
+    }
+}
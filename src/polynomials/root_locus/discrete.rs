@@ -0,0 +1,88 @@
+use num::{Complex, Float};
+
+/// Number of points used to approximate the z-plane design curves
+pub const CURVE_POINTS: usize = 256;
+
+/// Points of the constant damping ratio curve, the image of the `ζ` ray by `z = e^(sT)`
+///
+/// It is a logarithmic spiral going from `z = 1` (θ = 0) to the negative real
+/// axis (θ = π), with `|z| = e^(-ζ θ / √(1 - ζ²))` and `arg z = θ`. It does not
+/// depend on the sample time.
+#[must_use]
+pub fn damping_spiral<F: Float>(zeta: F, points: usize) -> Vec<Complex<F>> {
+    let one = F::one();
+
+    // The spiral degenerates into the real segment [0, 1]
+    if zeta >= one {
+        return vec![Complex::from(one), Complex::from(F::zero())];
+    }
+
+    let pi = F::from(std::f64::consts::PI).unwrap();
+    let last = F::from(points.max(2) - 1).unwrap();
+
+    (0..points.max(2))
+        .map(|i| damping_spiral_point(zeta, pi * F::from(i).unwrap() / last))
+        .collect()
+}
+
+/// Point of the constant damping ratio spiral at the angle `theta`, for `ζ < 1`
+#[must_use]
+pub fn damping_spiral_point<F: Float>(zeta: F, theta: F) -> Complex<F> {
+    let rate = zeta / (F::one() - zeta * zeta).sqrt();
+    Complex::from_polar((-rate * theta).exp(), theta)
+}
+
+/// Points of the constant natural frequency curve, the image by `z = e^(sT)` of the
+/// quarter circle `|s| = ωn` going from `jωn` to `-ωn`
+#[must_use]
+pub fn natural_frequency_contour<F: Float>(
+    omega_n: F,
+    sample_time: F,
+    points: usize,
+) -> Vec<Complex<F>> {
+    let half_pi = F::from(std::f64::consts::FRAC_PI_2).unwrap();
+    let last = F::from(points.max(2) - 1).unwrap();
+
+    (0..points.max(2))
+        .map(|i| {
+            let phi = half_pi + half_pi * F::from(i).unwrap() / last;
+            natural_frequency_point(omega_n, sample_time, phi)
+        })
+        .collect()
+}
+
+/// Point of the constant natural frequency curve, the image of `s = ωn e^(jφ)`
+#[must_use]
+pub fn natural_frequency_point<F: Float>(omega_n: F, sample_time: F, phi: F) -> Complex<F> {
+    Complex::from_polar(omega_n * sample_time, phi).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn spiral_matches_mapped_poles() {
+        // s = ωn (-ζ + j √(1 - ζ²)) for ζ = 0.5, ωn = 2, T = 0.1
+        let (zeta, omega_n, t) = (0.5f64, 2.0, 0.1);
+        let s = Complex::new(-zeta, zeta.mul_add(-zeta, 1.0).sqrt()) * omega_n;
+        let z = (s * t).exp();
+
+        let spiral = damping_spiral(zeta, 4096);
+        let closest = spiral
+            .iter()
+            .map(|x| (x - z).norm())
+            .fold(f64::INFINITY, f64::min);
+        assert!(closest < 1e-3);
+
+        let contour = natural_frequency_contour(omega_n, t, 4096);
+        let closest = contour
+            .iter()
+            .map(|x| (x - z).norm())
+            .fold(f64::INFINITY, f64::min);
+        assert!(closest < 1e-3);
+
+        assert_abs_diff_eq!(contour.last().unwrap().re, (-omega_n * t).exp());
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt::{Debug, Display};
+
+use num::Float;
+use rand::{distributions::Standard, prelude::Distribution};
+
+use super::RootLocus;
+
+/// A quantity of the closed-loop roots that can be plotted against the gain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GainPlotQuantity {
+    Real,
+    Imaginary,
+    Damping,
+    NaturalFrequency,
+}
+
+impl GainPlotQuantity {
+    pub const ALL: [Self; 4] = [
+        Self::Real,
+        Self::Imaginary,
+        Self::Damping,
+        Self::NaturalFrequency,
+    ];
+
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Real => "Re(p)",
+            Self::Imaginary => "Im(p)",
+            Self::Damping => "Damping",
+            Self::NaturalFrequency => "Natural frequency",
+        }
+    }
+}
+
+/// A root of a branch at a given gain, with its damping and natural frequency
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GainPlotPoint<F> {
+    pub gain: F,
+    /// Real part of the root, in the plane of the locus domain
+    pub real: F,
+    /// Imaginary part of the root, in the plane of the locus domain
+    pub imaginary: F,
+    /// `ζ = -Re(s) / |s|`, NaN for a root at `s = 0`
+    pub damping: F,
+    /// `ωn = |s|`
+    pub natural_frequency: F,
+}
+
+impl<F: Float> GainPlotPoint<F> {
+    #[must_use]
+    pub const fn value(&self, quantity: GainPlotQuantity) -> F {
+        match quantity {
+            GainPlotQuantity::Real => self.real,
+            GainPlotQuantity::Imaginary => self.imaginary,
+            GainPlotQuantity::Damping => self.damping,
+            GainPlotQuantity::NaturalFrequency => self.natural_frequency,
+        }
+    }
+}
+
+impl<F: Float + Display + Debug> RootLocus<F>
+where
+    Standard: Distribution<F>,
+{
+    /// Lists the stored samples of a branch as points for plots against `log k`
+    ///
+    /// The `k = 0` sample and roots at infinity are left out. In discrete time
+    /// the damping and natural frequency are those of `s = ln(z) / T`.
+    #[must_use]
+    pub fn gain_plot(&self, branch: usize) -> Vec<GainPlotPoint<F>> {
+        self.iter_branch(branch)
+            .filter(|(gain, root)| *gain > F::zero() && root.re.is_finite() && root.im.is_finite())
+            .map(|(gain, root)| {
+                let s = self.domain.to_s_plane(root);
+                let natural_frequency = s.norm();
+
+                GainPlotPoint {
+                    gain,
+                    real: root.re,
+                    imaginary: root.im,
+                    damping: -s.re / natural_frequency,
+                    natural_frequency,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::{
+        root_locus::{
+            options::{RootLocusOptions, Sampling},
+            Domain,
+        },
+        Polynomial,
+    };
+    use approx::assert_abs_diff_eq;
+    use num::Complex;
+
+    #[test]
+    fn second_order() {
+        // 1 / (s (s + 2)), above k = 1 the roots are -1 ± j √(k - 1), so ωn = √k and ζ = 1 / √k
+        let a = Polynomial::new(vec![0.0, 2.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+
+        let mut rl = RootLocus::new(a, b).unwrap();
+        rl.calculate_all(
+            &RootLocusOptions::default()
+                .with_gain_range(0.1, 100.0)
+                .with_sampling(Sampling::Geometric { ratio: 1.2 })
+                .with_precision(1e-12),
+        )
+        .unwrap();
+
+        let points = rl.gain_plot(0);
+        assert!(points.iter().all(|x| x.gain > 0.0));
+
+        for p in points.iter().filter(|x| x.gain > 1.01) {
+            assert_abs_diff_eq!(p.real, -1.0, epsilon = 1e-6);
+            assert_abs_diff_eq!(p.natural_frequency, p.gain.sqrt(), epsilon = 1e-6);
+            assert_abs_diff_eq!(p.damping, 1.0 / p.gain.sqrt(), epsilon = 1e-6);
+            assert_abs_diff_eq!(p.value(GainPlotQuantity::Imaginary), p.imaginary);
+        }
+    }
+
+    #[test]
+    fn discrete_maps_to_s_plane() {
+        // A single pole at z = e^(sT), with s = -1 + 2j and T = 0.1
+        let t = 0.1;
+        let s = Complex::new(-1.0, 2.0);
+        let z = (s * t).exp();
+
+        let domain = Domain::Discrete { sample_time: t };
+        let mapped = domain.to_s_plane(z);
+        assert_abs_diff_eq!(mapped.re, s.re, epsilon = 1e-12);
+        assert_abs_diff_eq!(mapped.im, s.im, epsilon = 1e-12);
+    }
+}
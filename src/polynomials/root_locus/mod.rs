@@ -0,0 +1,770 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Debug, Display},
+};
+
+use num::{Complex, Float};
+use rand::{distributions::Standard, prelude::Distribution, rngs::StdRng, Rng, SeedableRng};
+
+use self::{
+    delay::PadeDelay,
+    options::{RootLocusOptions, Sampling},
+};
+use crate::{
+    error::{Error, Result},
+    polynomials::{roots::RootFinding, Polynomial},
+    transfer_functions::TransferFunction,
+    NotNanFloat,
+};
+
+pub mod angles;
+pub mod curves;
+pub mod delay;
+pub mod design;
+pub mod discrete;
+pub mod gain_plots;
+pub mod options;
+pub mod samples;
+pub mod sensitivity;
+pub mod stability;
+
+/// Whether the locus lives in the s-plane or in the z-plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Domain<F> {
+    /// Stable roots lie in the left half plane
+    #[default]
+    Continuous,
+    /// Stable roots lie inside the unit circle, `z = e^(sT)`
+    Discrete { sample_time: F },
+}
+
+impl<F: Float> Domain<F> {
+    /// Checks whether `root` lies strictly inside the stable region
+    pub fn is_stable(&self, root: Complex<F>) -> bool {
+        match self {
+            Self::Continuous => root.re < F::zero(),
+            Self::Discrete { .. } => root.norm() < F::one(),
+        }
+    }
+
+    /// Maps a root to the s-plane, through `s = ln(z) / T` in discrete time
+    pub fn to_s_plane(&self, root: Complex<F>) -> Complex<F> {
+        match self {
+            Self::Continuous => root,
+            Self::Discrete { sample_time } => root.ln() / *sample_time,
+        }
+    }
+
+    /// `None` in continuous time
+    #[must_use]
+    pub const fn sample_time(&self) -> Option<F> {
+        match self {
+            Self::Continuous => None,
+            Self::Discrete { sample_time } => Some(*sample_time),
+        }
+    }
+}
+
+/// Returns the row of the sample in `gains` whose gain is closest to `gain`
+fn nearest_row<F: Float>(gains: &BTreeMap<NotNanFloat<F>, usize>, gain: F) -> Option<usize> {
+    let key = NotNanFloat::new(gain);
+    let below = gains.range(..=&key).next_back();
+    let above = gains.range(&key..).next();
+
+    match (below, above) {
+        (Some((a, &i)), Some((b, &j))) => {
+            if gain - a.0 <= b.0 - gain {
+                Some(i)
+            } else {
+                Some(j)
+            }
+        }
+        (Some((_, &i)), None) | (None, Some((_, &i))) => Some(i),
+        (None, None) => None,
+    }
+}
+
+/// Solves `poly` into `row`, whose length is the number of branches
+///
+/// When `poly` has a lower order than the number of branches, the remaining
+/// branches are at infinity: the finite roots are taken from the guesses with
+/// the smallest modulus, and the others are set to an infinite value. Guesses
+/// that are not finite (branches coming back from infinity) are moved onto
+/// the bound circle of the roots first. Without `warm`, the guesses are ignored.
+fn solve_row<F: Float + Debug>(
+    poly: &Polynomial<F>,
+    row: &mut [Complex<F>],
+    prec: F,
+    rng: &[F],
+    warm: bool,
+) where
+    Standard: Distribution<F>,
+{
+    let order = if poly.get_terms().len() > 1 {
+        poly.order().min(row.len())
+    } else {
+        0
+    };
+
+    let mut slots = (0..row.len()).collect::<Vec<_>>();
+    if order < row.len() {
+        let modulus = |x: Complex<F>| {
+            if x.re.is_finite() && x.im.is_finite() {
+                x.norm()
+            } else {
+                F::infinity()
+            }
+        };
+        slots.sort_by(|&i, &j| modulus(row[i]).partial_cmp(&modulus(row[j])).unwrap());
+    }
+    let (finite, infinite) = slots.split_at(order);
+
+    for &i in infinite {
+        row[i] = Complex::new(F::infinity(), F::zero());
+    }
+
+    let mut guesses = finite.iter().map(|&i| row[i]).collect::<Vec<_>>();
+
+    if order == 0 {
+        return;
+    }
+
+    if warm {
+        let (_, radius) = poly.determine_roots_bounds();
+        let angle = F::from(std::f64::consts::TAU).unwrap() / F::from(order.max(1)).unwrap();
+
+        for (i, x) in guesses.iter_mut().enumerate() {
+            if !x.re.is_finite() || !x.im.is_finite() {
+                *x = Complex::from_polar(
+                    radius,
+                    F::from(i).unwrap() * angle + F::from(0.5).unwrap(),
+                );
+            }
+        }
+
+        if rng.is_empty() {
+            poly.find_roots_from(&mut guesses, prec);
+        } else {
+            poly.find_roots_from_rng(&mut guesses, prec, rng);
+        }
+    } else {
+        poly.find_roots(&mut guesses, prec);
+    }
+
+    for (&i, x) in finite.iter().zip(guesses) {
+        row[i] = x;
+    }
+}
+
+/// Checks that `A + k B` is a valid characteristic polynomial for every gain
+fn check_open_loop<F: Float>(poly_a: &Polynomial<F>, poly_b: &Polynomial<F>) -> Result<()> {
+    if poly_a.is_zero() || poly_b.is_zero() {
+        return Err(Error::EmptyPolynomial);
+    }
+    if !poly_a.is_finite() || !poly_b.is_finite() {
+        return Err(Error::NonFiniteCoefficients);
+    }
+
+    Ok(())
+}
+
+/// A struct for calculating the roots of a polynomial A + k B,
+/// where A == `poly_a` and B == `poly_b` as k varies from 0 to infinity
+///
+/// There are as many branches as the highest order of A and B. Whenever
+/// `A + k B` has a lower order (at `k = 0` when B has the highest order, or
+/// where the leading coefficients cancel out), the missing roots are at
+/// infinity and are stored as roots with an infinite real part.
+pub struct RootLocus<F: Float> {
+    plant_a: Polynomial<F>, // open loop as given, before the delay is applied
+    plant_b: Polynomial<F>,
+    delay: Option<PadeDelay<F>>,
+    poly_a: Polynomial<F>,
+    poly_b: Polynomial<F>,
+    gains: BTreeMap<NotNanFloat<F>, usize>, // map from gain to row (sample) index in `roots`
+    roots: Vec<Complex<F>>,                 // single vec with all roots
+    branches: usize,
+    prec: F,        // precision used by the last `calculate_all`
+    jitter: Vec<F>, // random offsets used by the last `calculate_all`
+    domain: Domain<F>,
+}
+
+impl<F: Float + Display + Debug> RootLocus<F>
+where
+    Standard: Distribution<F>,
+{
+    /// Creates the locus of `A + k B`, where `B / A` is the open loop
+    ///
+    /// # Errors
+    ///
+    /// Fails if either polynomial is zero or has non-finite coefficients
+    pub fn new(poly_a: Polynomial<F>, poly_b: Polynomial<F>) -> Result<Self> {
+        check_open_loop(&poly_a, &poly_b)?;
+
+        let branches = poly_a.order().max(poly_b.order());
+        Ok(Self {
+            plant_a: poly_a.clone(),
+            plant_b: poly_b.clone(),
+            delay: None,
+            poly_a,
+            poly_b,
+            gains: BTreeMap::new(),
+            roots: Vec::new(),
+            branches,
+            prec: F::from(1e-6).unwrap(),
+            jitter: Vec::new(),
+            domain: Domain::Continuous,
+        })
+    }
+
+    /// Creates the locus of the loop closed around `k G`, with `A` the denominator
+    /// and `B` the numerator of `G`, in discrete time if `G` is
+    ///
+    /// # Errors
+    ///
+    /// Fails if the numerator is zero
+    pub fn from_open_loop(open_loop: &TransferFunction<F>) -> Result<Self> {
+        let rl = Self::new(
+            open_loop.get_denominator().clone(),
+            open_loop.get_numerator().clone(),
+        )?;
+
+        Ok(match open_loop.get_sample_time() {
+            Some(sample_time) => rl.with_domain(Domain::Discrete { sample_time }),
+            None => rl,
+        })
+    }
+
+    /// Sets the domain of the locus, which is continuous-time unless told otherwise
+    #[must_use]
+    pub const fn with_domain(mut self, domain: Domain<F>) -> Self {
+        self.domain = domain;
+        self
+    }
+
+    pub const fn set_domain(&mut self, domain: Domain<F>) {
+        self.domain = domain;
+    }
+
+    #[must_use]
+    pub const fn get_domain(&self) -> Domain<F> {
+        self.domain
+    }
+
+    #[must_use]
+    pub const fn get_branches(&self) -> usize {
+        self.branches
+    }
+
+    /// Computes `A + k B`, dropping the leading coefficients that cancel out
+    ///
+    /// A coefficient whose sum is below the rounding error of its two terms counts as zero
+    #[must_use]
+    pub fn characteristic(&self, gain: F) -> Polynomial<F> {
+        let (a, b) = (self.poly_a.get_terms(), self.poly_b.get_terms());
+        let tol = F::from(4).unwrap() * F::epsilon();
+
+        let mut terms = Vec::with_capacity(a.len().max(b.len()));
+        let mut len = 0;
+
+        for i in 0..a.len().max(b.len()) {
+            let x = a.get(i).copied().unwrap_or_else(F::zero);
+            let y = b.get(i).copied().unwrap_or_else(F::zero) * gain;
+            let sum = x + y;
+
+            if sum.abs() > tol * (x.abs() + y.abs()) {
+                len = i + 1;
+            }
+            terms.push(sum);
+        }
+
+        terms.truncate(len);
+        Polynomial::new(terms)
+    }
+
+    /// Closes the loop around `k B / A` with unity negative feedback, `k B / (A + k B)`,
+    /// in the domain of the locus
+    ///
+    /// # Errors
+    ///
+    /// Fails if `A + k B` is zero
+    pub fn closed_loop(&self, gain: F) -> Result<TransferFunction<F>> {
+        Ok(
+            TransferFunction::new(self.poly_b.clone() * gain, self.characteristic(gain))?
+                .with_sample_time(self.domain.sample_time()),
+        )
+    }
+
+    /// The open loop `B / A` at unit gain, in the domain of the locus
+    #[must_use]
+    pub fn open_loop(&self) -> TransferFunction<F> {
+        TransferFunction::new(self.poly_b.clone(), self.poly_a.clone())
+            .expect("the open loop is checked when set")
+            .with_sample_time(self.domain.sample_time())
+    }
+
+    /// Computes the gain `k` for a given `p` in `A(p) + k B(p) = 0`
+    ///
+    /// k = - A(p) / B(p)
+    pub fn compute_gain(&self, position: Complex<F>) -> Complex<F> {
+        -self.poly_a.eval_complex(position) / self.poly_b.eval_complex(position)
+    }
+
+    /// Returns the gain placing a closed-loop root at `point`, if it is on the locus,
+    /// i.e. if `compute_gain` is real and non-negative up to a relative tolerance
+    fn locus_gain(&self, point: Complex<F>) -> Option<F> {
+        let tol = F::epsilon().sqrt();
+        let gain = self.compute_gain(point);
+
+        (gain.re.is_finite()
+            && gain.re >= -tol
+            && gain.im.abs() <= tol * (F::one() + gain.re.abs()))
+        .then(|| gain.re.max(F::zero()))
+    }
+
+    /// Replaces the open-loop polynomials, e.g. after a single pole or zero was moved
+    ///
+    /// The stored samples are kept, and the next `calculate_all` warm-starts
+    /// every gain from them instead of starting from scratch. This only pays
+    /// off for small changes, so it is meant to be called once per frame while
+    /// dragging a root around.
+    ///
+    /// The delay set with `set_delay`, if any, is applied to the new open loop.
+    ///
+    /// # Errors
+    ///
+    /// Same as `RootLocus::new`, the locus is left untouched then
+    pub fn set_open_loop(&mut self, poly_a: Polynomial<F>, poly_b: Polynomial<F>) -> Result<()> {
+        check_open_loop(&poly_a, &poly_b)?;
+
+        self.plant_a = poly_a;
+        self.plant_b = poly_b;
+        self.refresh_open_loop();
+
+        Ok(())
+    }
+
+    /// Rebuilds the characteristic pair from the plant and the delay
+    fn refresh_open_loop(&mut self) {
+        (self.poly_a, self.poly_b) = match &self.delay {
+            Some(delay) => (
+                &self.plant_a * delay.get_denominator(),
+                &self.plant_b * delay.get_numerator(),
+            ),
+            None => (self.plant_a.clone(), self.plant_b.clone()),
+        };
+
+        let branches = self.poly_a.order().max(self.poly_b.order());
+        if branches != self.branches {
+            // The previous samples have a different shape, they are useless as guesses
+            self.clear_samples();
+            self.branches = branches;
+        }
+    }
+
+    /// Samples the locus for `k = 0` and the gains selected by `options`
+    ///
+    /// If samples from a previous call are stored (see `set_open_loop`), each gain
+    /// is warm-started from the previous roots at the nearest gain, otherwise
+    /// from the roots of the preceding gain
+    ///
+    /// # Errors
+    ///
+    /// `Error::InvalidParameter` if the options are invalid (see
+    /// `RootLocusOptions::validate`), in which case the stored samples are left as they are,
+    /// `Error::SolverDivergence` if the root finder returns NaN roots, and
+    /// `Error::EmptyPolynomial` if `A + k B` vanishes for some positive gain, i.e. if
+    /// B is a negative multiple of A, in which case no samples are kept
+    pub fn calculate_all(&mut self, options: &RootLocusOptions<F>) -> Result<()> {
+        options.validate()?;
+
+        let prec = options.get_precision();
+
+        self.prec = prec;
+        self.jitter = self.jitter_of(options);
+        let rng = self.jitter.clone();
+
+        let previous_gains = std::mem::take(&mut self.gains);
+        let previous_roots = std::mem::take(&mut self.roots);
+        let warm = !previous_gains.is_empty();
+
+        // Add the first point
+        self.roots.resize(self.branches, Complex::from(F::zero()));
+        // First of all calculate for k == 0.0
+        self.gains
+            .insert(NotNanFloat::new(F::from(0.0).unwrap()), 0);
+        if warm {
+            self.roots[..self.branches].copy_from_slice(&previous_roots[..self.branches]);
+        }
+        solve_row(
+            &self.characteristic(F::zero()),
+            &mut self.roots[..self.branches],
+            prec,
+            &rng,
+            warm,
+        );
+
+        // gains to calculate
+        let mut future_gains = BTreeSet::new();
+
+        if options.get_include_infinite_endpoints() {
+            future_gains.insert(NotNanFloat::new(options.get_endpoint_gain()));
+        }
+
+        if options.get_include_breakaways() {
+            let intersections_poly =
+                &self.poly_a.derivative() * &self.poly_b - &self.poly_b.derivative() * &self.poly_a;
+
+            // `A'B - B'A` vanishes when `B` is a multiple of `A`, there are no breakaways then
+            let intersections = if intersections_poly.is_zero() {
+                Vec::new()
+            } else {
+                match intersections_poly.try_find_roots(prec) {
+                    Ok(x) => x,
+                    Err(e) => {
+                        self.clear_samples();
+                        return Err(e);
+                    }
+                }
+            };
+
+            intersections
+                .iter()
+                .copied()
+                .map(|x| self.compute_gain(x).re)
+                .filter(|x| !x.is_nan())
+                .map(|x| NotNanFloat::new(x))
+                .filter(|x| x.0.is_sign_positive())
+                .for_each(|x| {
+                    future_gains.insert(x);
+                });
+        }
+
+        for k in options.grid_gains() {
+            future_gains.insert(NotNanFloat::new(k));
+        }
+
+        if let Sampling::Adaptive { max_step } = options.get_sampling() {
+            let (min_gain, max_gain) = options.get_gain_range();
+            for k in self.adaptive_gains(min_gain, max_gain, max_step, prec, &rng) {
+                future_gains.insert(NotNanFloat::new(k));
+            }
+        }
+
+        match self.cancellation_gain() {
+            Ok(Some(gain)) => {
+                future_gains.insert(NotNanFloat::new(gain));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.clear_samples();
+                return Err(e);
+            }
+        }
+
+        // k = 0 already is the first row
+        future_gains.remove(&NotNanFloat::new(F::zero()));
+
+        // Given the size of future_gains, resize self.roots again
+        self.roots.resize(
+            (1 + future_gains.len()) * self.branches,
+            Complex::from(F::zero()),
+        );
+
+        let mut old_roots = vec![Complex::from(F::zero()); self.branches];
+        old_roots.copy_from_slice(&self.roots[..self.branches]);
+
+        for (i, gain) in future_gains.iter().enumerate() {
+            let poly = self.characteristic(gain.0);
+
+            if let Some(row) = nearest_row(&previous_gains, gain.0) {
+                old_roots.copy_from_slice(
+                    &previous_roots[row * self.branches..(row + 1) * self.branches],
+                );
+            }
+
+            solve_row(&poly, &mut old_roots, prec, &rng, true);
+
+            self.roots[(i + 1) * self.branches..(i + 2) * self.branches]
+                .copy_from_slice(&old_roots);
+            self.gains.insert(NotNanFloat::new(gain.0), i + 1);
+        }
+
+        // Calculate for infinite gain
+        // We can approximate all roots and calculate the exact location of the finite roots
+        // And then replace the approximated values for the exact ones, in their respective positions
+        // These steps should be necessary to keep the roots order
+
+        // Roots at infinity are expected, NaN are not
+        if self.roots.iter().any(|x| x.re.is_nan() || x.im.is_nan()) {
+            self.clear_samples();
+            Err(Error::SolverDivergence)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The offsets of the initial guesses, given by `options` or drawn from its seed
+    fn jitter_of(&self, options: &RootLocusOptions<F>) -> Vec<F> {
+        if options.get_jitter().is_empty() {
+            options.get_seed().map_or_else(Vec::new, |seed| {
+                let mut rng = StdRng::seed_from_u64(seed);
+                (0..2 * self.branches).map(|_| rng.gen()).collect()
+            })
+        } else {
+            options.get_jitter().to_vec()
+        }
+    }
+
+    /// The positive gain where the leading coefficients of `A + k B` cancel out, where a
+    /// branch goes through infinity
+    ///
+    /// Fails with `Error::EmptyPolynomial` when every coefficient cancels out there,
+    /// i.e. when B is proportional to A
+    fn cancellation_gain(&self) -> Result<Option<F>> {
+        let (a, b) = (self.poly_a.get_terms(), self.poly_b.get_terms());
+        if a.len() != b.len() {
+            return Ok(None);
+        }
+
+        let gain = -a[a.len() - 1] / b[b.len() - 1];
+        if gain <= F::zero() {
+            return Ok(None);
+        }
+        if self.characteristic(gain).is_zero() {
+            return Err(Error::EmptyPolynomial);
+        }
+
+        Ok(Some(gain))
+    }
+
+    fn clear_samples(&mut self) {
+        self.gains.clear();
+        self.roots.clear();
+    }
+
+    #[must_use]
+    pub fn get_roots(&self) -> &[Complex<F>] {
+        &self.roots
+    }
+
+    /// Map from every sampled gain to its row in `get_roots`,
+    /// the roots of that sample being `roots[row * branches..(row + 1) * branches]`
+    #[must_use]
+    pub const fn get_gains(&self) -> &BTreeMap<NotNanFloat<F>, usize> {
+        &self.gains
+    }
+
+    /// Computes the roots of `A + k B` for an arbitrary gain `k`
+    ///
+    /// The solver is warm-started from the stored sample with the closest gain,
+    /// so the roots are returned in the same branch order as `get_roots`
+    #[must_use]
+    pub fn roots_at_gain(&self, gain: F) -> Vec<Complex<F>> {
+        let poly = self.characteristic(gain);
+        let mut roots = vec![Complex::from(F::zero()); self.branches];
+
+        let row = nearest_row(&self.gains, gain);
+        if let Some(row) = row {
+            roots.copy_from_slice(&self.roots[row * self.branches..(row + 1) * self.branches]);
+        }
+        solve_row(&poly, &mut roots, self.prec, &self.jitter, row.is_some());
+
+        roots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::{root_locus::stability::GainInterval, roots::PolynomialRoot};
+
+    fn third_order() -> RootLocus<f64> {
+        // 1 / (s (s + 1) (s + 2))
+        let a = Polynomial::new(vec![0.0, 2.0, 3.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+
+        RootLocus::new(a, b).unwrap()
+    }
+
+    fn options(prec: f64) -> RootLocusOptions<f64> {
+        RootLocusOptions::default()
+            .with_gain_range(0.01, 100.0)
+            .with_sampling(Sampling::Geometric { ratio: 1.1 })
+            .with_precision(prec)
+    }
+
+    #[test]
+    fn every_sample_has_a_gain() {
+        let mut rl = third_order();
+        rl.calculate_all(&options(1e-9)).unwrap();
+
+        let rows = rl.get_roots().len() / rl.get_branches();
+        assert_eq!(rl.get_gains().len(), rows);
+
+        let mut indices = rl.get_gains().values().copied().collect::<Vec<_>>();
+        indices.sort_unstable();
+        assert_eq!(indices, (0..rows).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn warm_start_matches_cold_start() {
+        let mut warm = third_order();
+        warm.calculate_all(&options(1e-12)).unwrap();
+
+        // Move the pole at -2 to -2.1
+        let a = Polynomial::from_roots(
+            1.0,
+            &[
+                PolynomialRoot::RealSingle(0.0),
+                PolynomialRoot::RealSingle(-1.0),
+                PolynomialRoot::RealSingle(-2.1),
+            ],
+        );
+        let b = Polynomial::new(vec![1.0]);
+
+        warm.set_open_loop(a.clone(), b.clone()).unwrap();
+        warm.calculate_all(&options(1e-12)).unwrap();
+
+        let mut cold = RootLocus::new(a, b).unwrap();
+        cold.calculate_all(&options(1e-12)).unwrap();
+
+        assert_eq!(warm.get_gains(), cold.get_gains());
+        for (w, c) in warm.iter_samples().zip(cold.iter_samples()) {
+            for r in w.roots {
+                assert!(c.roots.iter().any(|x| (x - r).norm() < 1e-6));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_open_loops() {
+        let a = Polynomial::new(vec![1.0, 1.0]);
+
+        assert_eq!(
+            RootLocus::new(a.clone(), Polynomial::new(vec![0.0])).err(),
+            Some(Error::EmptyPolynomial)
+        );
+
+        let mut rl = third_order();
+        assert_eq!(
+            rl.set_open_loop(Polynomial::new(vec![f64::NAN, 1.0]), a),
+            Err(Error::NonFiniteCoefficients)
+        );
+        assert_eq!(rl.get_branches(), 3);
+    }
+
+    #[test]
+    fn from_open_loop() {
+        // 1 / (s (s + 1)) in series with 1 / (s + 2)
+        let plant = TransferFunction::from_coefficients(vec![1.0], vec![0.0, 1.0, 1.0]).unwrap();
+        let sensor = TransferFunction::from_coefficients(vec![1.0], vec![2.0, 1.0]).unwrap();
+
+        let mut chained = RootLocus::from_open_loop(&(plant * sensor)).unwrap();
+        chained.calculate_all(&options(1e-12)).unwrap();
+
+        let mut expected = third_order();
+        expected.calculate_all(&options(1e-12)).unwrap();
+
+        assert_eq!(chained.get_gains(), expected.get_gains());
+        for (c, e) in chained.iter_samples().zip(expected.iter_samples()) {
+            for r in c.roots {
+                assert!(e.roots.iter().any(|x| (x - r).norm() < 1e-6));
+            }
+        }
+    }
+
+    #[test]
+    fn improper_open_loop() {
+        // (s + 2) (s + 3) / (s + 1), one branch comes in from infinity
+        let a = Polynomial::new(vec![1.0, 1.0]);
+        let b = Polynomial::new(vec![6.0, 5.0, 1.0]);
+
+        let mut rl = RootLocus::new(a, b).unwrap();
+        rl.calculate_all(&options(1e-12)).unwrap();
+        assert_eq!(rl.get_branches(), 2);
+
+        let first = rl.iter_samples().next().unwrap();
+        assert_eq!(first.roots.iter().filter(|x| x.re.is_infinite()).count(), 1);
+        assert!(first.roots.iter().any(|x| (x + 1.0).norm() < 1e-9));
+
+        // Every other sample is finite, and the branches end at the zeros
+        assert!(rl
+            .iter_samples()
+            .skip(1)
+            .all(|x| x.roots.iter().all(|r| r.re.is_finite())));
+        let last = rl.iter_samples().last().unwrap();
+        for z in [-2.0, -3.0] {
+            assert!(last.roots.iter().any(|x| (x - z).norm() < 1e-6));
+        }
+    }
+
+    #[test]
+    fn leading_coefficients_cancel() {
+        // (1 - s²) / (s² + 3s + 2), at k = 1 the locus polynomial is 3s + 3
+        let a = Polynomial::new(vec![2.0, 3.0, 1.0]);
+        let b = Polynomial::new(vec![1.0, 0.0, -1.0]);
+
+        let mut rl = RootLocus::new(a, b).unwrap();
+        rl.calculate_all(&options(1e-12)).unwrap();
+
+        assert!(rl.get_gains().contains_key(&NotNanFloat::new(1.0)));
+        assert_eq!(rl.characteristic(1.0).order(), 1);
+
+        let roots = rl.roots_at_gain(1.0);
+        assert_eq!(roots.iter().filter(|x| x.re.is_infinite()).count(), 1);
+        assert!(roots.iter().any(|x| (x + 1.0).norm() < 1e-9));
+
+        // The branch comes back from infinity right after
+        let roots = rl.roots_at_gain(1.5);
+        let poly = rl.characteristic(1.5);
+        assert!(roots.iter().all(|&x| poly.eval_complex(x).norm() < 1e-6));
+
+        // (1 - k) s² + 3s + (2 + k) never crosses the axis, it turns unstable through infinity
+        let stability = rl.imaginary_axis_crossings();
+        assert!(stability.crossings.is_empty());
+        assert_eq!(
+            stability.stable_gains,
+            vec![GainInterval { min: 0.0, max: 1.0 }]
+        );
+        assert!(rl.is_stable_at(0.5));
+        assert!(!rl.is_stable_at(2.0));
+    }
+
+    #[test]
+    fn proportional_open_loop() {
+        // -(s + 1) / (s + 1), the locus polynomial vanishes at k = 1
+        let a = Polynomial::new(vec![1.0, 1.0]);
+        let b = Polynomial::new(vec![-1.0, -1.0]);
+
+        let mut rl = RootLocus::new(a, b).unwrap();
+        assert!(rl.characteristic(1.0).is_zero());
+        assert_eq!(
+            rl.calculate_all(&options(1e-12)),
+            Err(Error::EmptyPolynomial)
+        );
+        assert!(rl.get_gains().is_empty());
+
+        assert!(rl.is_stable_at(0.5));
+        assert!(!rl.is_stable_at(1.0));
+        assert!(rl.closed_loop(1.0).is_err());
+    }
+
+    #[test]
+    fn roots_at_gain() {
+        let mut rl = third_order();
+        rl.calculate_all(&options(1e-9)).unwrap();
+
+        let roots = rl.roots_at_gain(6.0);
+
+        // s³ + 3s² + 2s + 6 = (s + 3) (s² + 2)
+        let expected = [
+            Complex::new(-3.0, 0.0),
+            Complex::new(0.0, 2f64.sqrt()),
+            Complex::new(0.0, -(2f64.sqrt())),
+        ];
+        for e in expected {
+            assert!(roots.iter().any(|r| (r - e).norm() < 1e-6));
+        }
+    }
+}
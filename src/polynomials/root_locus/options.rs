@@ -0,0 +1,310 @@
+use num::Float;
+
+use crate::error::{Error, Result};
+
+/// How the gains between the ends of the gain range are spread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sampling<F> {
+    /// Each gain is the previous one multiplied by `ratio`, denser near the open-loop poles
+    Geometric { ratio: F },
+    /// Each gain is the previous one plus `step`
+    Linear { step: F },
+    /// The step follows the root sensitivity `dp/dk`, so that the roots move by
+    /// about `max_step` between samples
+    Adaptive { max_step: F },
+}
+
+/// Settings of `RootLocus::calculate_all`
+///
+/// Built from `RootLocusOptions::default()` with the `with_*` methods. The values
+/// are checked by `RootLocusOptions::validate` when the locus is calculated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootLocusOptions<F> {
+    min_gain: F,
+    max_gain: F,
+    sampling: Sampling<F>,
+    precision: F,
+    include_breakaways: bool,
+    include_infinite_endpoints: bool,
+    endpoint_gain: F,
+    seed: Option<u64>,
+    jitter: Vec<F>,
+}
+
+impl<F: Float> Default for RootLocusOptions<F> {
+    /// Gains from 0.01 to 1000 with a 1% geometric step, plus the breakaways and
+    /// an endpoint at `k = 1e12`, solved to a precision of `1e-6`
+    fn default() -> Self {
+        Self {
+            min_gain: F::from(0.01).unwrap(),
+            max_gain: F::from(1000).unwrap(),
+            sampling: Sampling::Geometric {
+                ratio: F::from(1.01).unwrap(),
+            },
+            precision: F::from(1e-6).unwrap(),
+            include_breakaways: true,
+            include_infinite_endpoints: true,
+            endpoint_gain: F::from(1e12).unwrap(),
+            seed: Some(0),
+            jitter: Vec::new(),
+        }
+    }
+}
+
+impl<F: Float> RootLocusOptions<F> {
+    /// Sets the range sampled with the sampling strategy, `k = 0` is always sampled
+    #[must_use]
+    pub const fn with_gain_range(mut self, min_gain: F, max_gain: F) -> Self {
+        self.min_gain = min_gain;
+        self.max_gain = max_gain;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_sampling(mut self, sampling: Sampling<F>) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Sets the precision of the root finder, the largest squared step of a converged iteration
+    #[must_use]
+    pub const fn with_precision(mut self, precision: F) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Whether the gains of the breakaway and break-in points are sampled too
+    #[must_use]
+    pub const fn with_breakaways(mut self, include: bool) -> Self {
+        self.include_breakaways = include;
+        self
+    }
+
+    /// Whether a very large gain is sampled, so the branches reach the zeros or go off to infinity
+    #[must_use]
+    pub const fn with_infinite_endpoints(mut self, include: bool) -> Self {
+        self.include_infinite_endpoints = include;
+        self
+    }
+
+    /// Sets the gain standing for infinity when the infinite endpoints are included
+    #[must_use]
+    pub const fn with_endpoint_gain(mut self, gain: F) -> Self {
+        self.endpoint_gain = gain;
+        self
+    }
+
+    /// Sets the seed of the small random offsets added to the initial guesses, which
+    /// keep the solver from stalling on coincident guesses
+    ///
+    /// `None` disables the offsets.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Uses the given random offsets, in `[0, 1)`, for the initial guesses instead of
+    /// drawing them from the seed
+    ///
+    /// An empty vector goes back to the seed.
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: Vec<F>) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    #[must_use]
+    pub const fn get_gain_range(&self) -> (F, F) {
+        (self.min_gain, self.max_gain)
+    }
+
+    #[must_use]
+    pub const fn get_sampling(&self) -> Sampling<F> {
+        self.sampling
+    }
+
+    #[must_use]
+    pub const fn get_precision(&self) -> F {
+        self.precision
+    }
+
+    #[must_use]
+    pub const fn get_include_breakaways(&self) -> bool {
+        self.include_breakaways
+    }
+
+    #[must_use]
+    pub const fn get_include_infinite_endpoints(&self) -> bool {
+        self.include_infinite_endpoints
+    }
+
+    #[must_use]
+    pub const fn get_endpoint_gain(&self) -> F {
+        self.endpoint_gain
+    }
+
+    #[must_use]
+    pub const fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    #[must_use]
+    pub fn get_jitter(&self) -> &[F] {
+        &self.jitter
+    }
+
+    /// Checks that the gain range is non-negative and ordered, and that the sampling
+    /// step, the precision and the endpoint gain are positive (a geometric ratio
+    /// must exceed 1)
+    ///
+    /// # Errors
+    ///
+    /// `Error::InvalidParameter` with the first requirement that is not met
+    pub fn validate(&self) -> Result<()> {
+        let check = |ok: bool, reason: &'static str| {
+            if ok {
+                Ok(())
+            } else {
+                Err(Error::InvalidParameter(reason))
+            }
+        };
+        let positive = |x: F| x > F::zero();
+
+        check(
+            self.min_gain >= F::zero() && self.min_gain <= self.max_gain,
+            "the gain range must be non-negative and ordered",
+        )?;
+        match self.sampling {
+            Sampling::Geometric { ratio } => check(ratio > F::one(), "the ratio must exceed 1")?,
+            Sampling::Linear { step: x } | Sampling::Adaptive { max_step: x } => {
+                check(positive(x), "the step must be positive")?;
+            }
+        }
+        check(positive(self.precision), "the precision must be positive")?;
+        check(
+            positive(self.endpoint_gain),
+            "the endpoint gain must be positive",
+        )
+    }
+
+    /// The gains of the sampling strategy inside the gain range, in increasing order
+    ///
+    /// A geometric sampling cannot start from `k = 0`, so it starts from `ratio - 1` then.
+    /// The adaptive sampling depends on the locus, it has no fixed gains, and invalid
+    /// options have none either.
+    #[must_use]
+    pub fn grid_gains(&self) -> Vec<F> {
+        let mut out = Vec::new();
+
+        if self.validate().is_err() {
+            return out;
+        }
+
+        match self.sampling {
+            Sampling::Geometric { ratio } => {
+                let mut k = if self.min_gain.is_zero() {
+                    ratio - F::one()
+                } else {
+                    self.min_gain
+                };
+                while k < self.max_gain {
+                    out.push(k);
+                    k = k * ratio;
+                }
+            }
+            Sampling::Linear { step } => {
+                let mut i = F::zero();
+                let mut k = self.min_gain;
+                while k < self.max_gain {
+                    out.push(k);
+                    i = i + F::one();
+                    k = self.min_gain + step * i;
+                }
+            }
+            Sampling::Adaptive { .. } => {}
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::{root_locus::RootLocus, Polynomial};
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn grid_gains() {
+        let linear = RootLocusOptions::default()
+            .with_gain_range(0.0, 1.0)
+            .with_sampling(Sampling::Linear { step: 0.25 });
+        assert_eq!(linear.grid_gains(), [0.0, 0.25, 0.5, 0.75]);
+
+        let geometric = RootLocusOptions::default()
+            .with_gain_range(1.0, 10.0)
+            .with_sampling(Sampling::Geometric { ratio: 2.0 });
+        assert_eq!(geometric.grid_gains(), [1.0, 2.0, 4.0, 8.0]);
+
+        // Only a zero start is moved, even below `ratio - 1`
+        let geometric = RootLocusOptions::default()
+            .with_gain_range(0.1, 0.5)
+            .with_sampling(Sampling::Geometric { ratio: 2.0 });
+        assert_eq!(geometric.grid_gains(), [0.1, 0.2, 0.4]);
+
+        let geometric = RootLocusOptions::default()
+            .with_gain_range(0.0, 4.0)
+            .with_sampling(Sampling::Geometric { ratio: 2.0 });
+        assert_eq!(geometric.grid_gains(), [1.0, 2.0]);
+    }
+
+    #[test]
+    fn invalid_options() {
+        let a = Polynomial::new(vec![0.0, 2.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+        let mut rl = RootLocus::new(a, b).unwrap();
+
+        for options in [
+            RootLocusOptions::default().with_gain_range(2.0, 1.0),
+            RootLocusOptions::default().with_sampling(Sampling::Geometric { ratio: 1.0 }),
+            RootLocusOptions::default().with_sampling(Sampling::Linear { step: 0.0 }),
+            RootLocusOptions::default().with_sampling(Sampling::Adaptive { max_step: -1.0 }),
+            RootLocusOptions::default().with_precision(f64::NAN),
+            RootLocusOptions::default().with_endpoint_gain(0.0),
+        ] {
+            assert!(matches!(
+                rl.calculate_all(&options),
+                Err(Error::InvalidParameter(_))
+            ));
+            assert!(options.grid_gains().is_empty());
+        }
+    }
+
+    #[test]
+    fn optional_samples() {
+        // 1 / (s (s + 2)) breaks away at s = -1, k = 1
+        let a = Polynomial::new(vec![0.0, 2.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+        let mut rl = RootLocus::new(a, b).unwrap();
+
+        let options = RootLocusOptions::default()
+            .with_gain_range(0.5, 2.0)
+            .with_sampling(Sampling::Linear { step: 0.75 });
+
+        rl.calculate_all(&options).unwrap();
+        let gains = rl.get_gains().keys().map(|x| x.0).collect::<Vec<_>>();
+        assert_eq!(gains.len(), 5);
+        assert_abs_diff_eq!(gains[2], 1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(gains[4], 1e12);
+
+        rl.calculate_all(
+            &options
+                .with_breakaways(false)
+                .with_infinite_endpoints(false),
+        )
+        .unwrap();
+        let gains = rl.get_gains().keys().map(|x| x.0).collect::<Vec<_>>();
+        assert_eq!(gains, [0.0, 0.5, 1.25]);
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt::{Debug, Display};
+
+use num::{Complex, Float};
+use rand::{distributions::Standard, prelude::Distribution};
+
+use super::RootLocus;
+
+/// The closed-loop roots of every branch at a single gain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample<'a, F> {
+    pub gain: F,
+    /// One root per branch, indexed by branch
+    pub roots: &'a [Complex<F>],
+}
+
+impl<F: Float + Display + Debug> RootLocus<F>
+where
+    Standard: Distribution<F>,
+{
+    /// Iterates over the stored samples in increasing gain order
+    pub fn iter_samples(&self) -> impl Iterator<Item = Sample<'_, F>> + '_ {
+        self.gains.iter().map(|(gain, &row)| Sample {
+            gain: gain.0,
+            roots: &self.roots[row * self.branches..(row + 1) * self.branches],
+        })
+    }
+
+    /// Iterates over the `(gain, root)` points of a single branch in increasing gain order
+    pub fn iter_branch(&self, branch: usize) -> impl Iterator<Item = (F, Complex<F>)> + '_ {
+        assert!(branch < self.branches, "branch index out of range");
+
+        self.iter_samples().map(move |x| (x.gain, x.roots[branch]))
+    }
+
+    /// Iterates over every branch, each one yielding its `(gain, root)` points
+    pub fn iter_branches(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = (F, Complex<F>)> + '_> + '_ {
+        (0..self.branches).map(move |i| self.iter_branch(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::polynomials::{
+        root_locus::options::{RootLocusOptions, Sampling},
+        Polynomial,
+    };
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn branches_follow_samples() {
+        // 1 / ((s + 1) (s + 3))
+        let a = Polynomial::new(vec![3.0, 4.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+
+        let mut rl = RootLocus::new(a, b).unwrap();
+        rl.calculate_all(
+            &RootLocusOptions::default()
+                .with_gain_range(0.1, 10.0)
+                .with_sampling(Sampling::Geometric { ratio: 1.5 })
+                .with_precision(1e-9),
+        )
+        .unwrap();
+
+        let samples = rl.iter_samples().collect::<Vec<_>>();
+        assert!(samples.windows(2).all(|w| w[0].gain < w[1].gain));
+        assert_eq!(samples[0].gain, 0.0);
+
+        for (i, branch) in rl.iter_branches().enumerate() {
+            for ((gain, root), sample) in branch.zip(&samples) {
+                assert_eq!(gain, sample.gain);
+                assert_eq!(root, sample.roots[i]);
+            }
+        }
+    }
+}
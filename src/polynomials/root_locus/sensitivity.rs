@@ -0,0 +1,157 @@
+use std::fmt::{Debug, Display};
+
+use num::{Complex, Float, Zero};
+use rand::{distributions::Standard, prelude::Distribution};
+
+use super::{solve_row, RootLocus};
+
+/// Most samples the adaptive sampling takes, whatever the step
+const MAX_ADAPTIVE_SAMPLES: usize = 100_000;
+
+/// A point of a branch along with its sensitivity to the gain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BranchPoint<F> {
+    pub gain: F,
+    pub root: Complex<F>,
+    /// `dp/dk` at this point
+    pub sensitivity: Complex<F>,
+}
+
+impl<F: Float + Display + Debug> RootLocus<F>
+where
+    Standard: Distribution<F>,
+{
+    /// Computes how fast a closed-loop root moves with the gain, `dp/dk`
+    ///
+    /// Differentiating `A(p) + k B(p) = 0` gives `dp/dk = -B(p) / (A'(p) + k B'(p))`.
+    /// It blows up at the breakaway points, where roots collide, and is infinite
+    /// for roots at infinity.
+    #[must_use]
+    pub fn sensitivity(&self, gain: F, root: Complex<F>) -> Complex<F> {
+        if !root.re.is_finite() || !root.im.is_finite() {
+            return Complex::new(F::infinity(), F::zero());
+        }
+
+        let den = self.poly_a.eval_complex_derivative(root)
+            + self.poly_b.eval_complex_derivative(root) * gain;
+        if den.is_zero() {
+            return Complex::new(F::infinity(), F::zero());
+        }
+
+        -self.poly_b.eval_complex(root) / den
+    }
+
+    /// Iterates over the points of a single branch in increasing gain order, along
+    /// with their sensitivity
+    pub fn iter_branch_sensitivity(
+        &self,
+        branch: usize,
+    ) -> impl Iterator<Item = BranchPoint<F>> + '_ {
+        self.iter_branch(branch)
+            .map(move |(gain, root)| BranchPoint {
+                gain,
+                root,
+                sensitivity: self.sensitivity(gain, root),
+            })
+    }
+
+    /// Picks gains from `min_gain` to `max_gain` so that no root moves by more
+    /// than about `max_step` from one gain to the next
+    ///
+    /// Each step is `max_step / max |dp/dk|`, kept between a thousandth of the
+    /// gain and the gain itself, so that breakaways (where `dp/dk` is infinite)
+    /// are still crossed and far gains do not get too sparse.
+    pub(super) fn adaptive_gains(
+        &self,
+        min_gain: F,
+        max_gain: F,
+        max_step: F,
+        prec: F,
+        rng: &[F],
+    ) -> Vec<F> {
+        let min_ratio = F::from(1e-3).unwrap();
+
+        let mut out = Vec::new();
+        let mut row = vec![Complex::zero(); self.branches];
+        let mut k = min_gain;
+
+        solve_row(&self.characteristic(k), &mut row, prec, rng, false);
+
+        while k < max_gain && out.len() < MAX_ADAPTIVE_SAMPLES {
+            out.push(k);
+
+            let fastest = row
+                .iter()
+                .map(|&p| self.sensitivity(k, p).norm())
+                .filter(|x| x.is_finite())
+                .fold(F::zero(), F::max);
+
+            let step = if fastest.is_zero() {
+                F::infinity()
+            } else {
+                max_step / fastest
+            };
+            let lower = (k * min_ratio).max(F::epsilon());
+            let upper = if k > F::zero() { k } else { F::infinity() };
+
+            k = k + step.max(lower).min(upper);
+            solve_row(&self.characteristic(k), &mut row, prec, rng, true);
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::{
+        root_locus::options::{RootLocusOptions, Sampling},
+        Polynomial,
+    };
+    use approx::assert_abs_diff_eq;
+
+    fn second_order() -> RootLocus<f64> {
+        // 1 / (s (s + 2)), with roots -1 ± √(1 - k)
+        let a = Polynomial::new(vec![0.0, 2.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+
+        RootLocus::new(a, b).unwrap()
+    }
+
+    #[test]
+    fn matches_closed_form() {
+        let rl = second_order();
+
+        // p = -1 + √(1 - k), so dp/dk = -1 / (2 √(1 - k))
+        let dp = rl.sensitivity(0.75, Complex::new(-0.5, 0.0));
+        assert_abs_diff_eq!(dp.re, -1.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(dp.im, 0.0, epsilon = 1e-12);
+
+        // Both roots collide at the breakaway point
+        assert!(rl
+            .sensitivity(1.0, Complex::new(-1.0, 0.0))
+            .re
+            .is_infinite());
+    }
+
+    #[test]
+    fn adaptive_sampling() {
+        let mut rl = second_order();
+        rl.calculate_all(
+            &RootLocusOptions::default()
+                .with_gain_range(0.01, 10.0)
+                .with_sampling(Sampling::Adaptive { max_step: 0.05 })
+                .with_infinite_endpoints(false),
+        )
+        .unwrap();
+
+        // No root jumps much further than the allowed step, even around the breakaway
+        for branch in 0..rl.get_branches() {
+            let points = rl.iter_branch_sensitivity(branch).collect::<Vec<_>>();
+            for w in points.windows(2) {
+                assert!((w[1].root - w[0].root).norm() < 0.2);
+            }
+        }
+    }
+}
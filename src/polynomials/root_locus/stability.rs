@@ -0,0 +1,282 @@
+use std::fmt::{Debug, Display};
+
+use num::{Complex, Float, Zero};
+use rand::{distributions::Standard, prelude::Distribution};
+
+use super::{curves::Curve, Domain, RootLocus};
+use crate::polynomials::roots::RootFinding;
+
+/// A point where a branch crosses the stability boundary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AxisCrossing<F> {
+    /// The crossing in the upper half plane, `jω` or `e^(jωT)` depending on the domain
+    pub point: Complex<F>,
+    /// Crossing frequency
+    pub omega: F,
+    /// Gain at which the crossing occurs
+    pub gain: F,
+}
+
+impl<F: Float> AxisCrossing<F> {
+    /// Angle of the crossing point, which for the unit circle is `ωT`
+    #[must_use]
+    pub fn angle(&self) -> F {
+        self.point.arg()
+    }
+}
+
+/// A closed interval of gains, `max` may be infinite
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GainInterval<F> {
+    pub min: F,
+    pub max: F,
+}
+
+impl<F: Float> GainInterval<F> {
+    #[must_use]
+    pub fn contains(&self, gain: F) -> bool {
+        self.min <= gain && gain <= self.max
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StabilityCrossings<F> {
+    /// Domain whose stability boundary (imaginary axis or unit circle) was used
+    pub domain: Domain<F>,
+    /// Every crossing with `ω >= 0`, sorted by gain
+    pub crossings: Vec<AxisCrossing<F>>,
+    /// Gain intervals for which every closed-loop root lies in the stable region
+    pub stable_gains: Vec<GainInterval<F>>,
+}
+
+impl<F: Float + Display + Debug> RootLocus<F>
+where
+    Standard: Distribution<F>,
+{
+    /// Computes where the branches cross the imaginary axis, and the resulting stable gains
+    ///
+    /// Writing `A(jω) = Ar(ω) + j Ai(ω)` (and the same for B), both
+    /// `Ar + k Br = 0` and `Ai + k Bi = 0` must hold, so every crossing
+    /// frequency is a real root of `Ar Bi - Ai Br`, and its gain is `-A(jω) / B(jω)`
+    #[must_use]
+    pub fn imaginary_axis_crossings(&self) -> StabilityCrossings<F> {
+        let tol = F::epsilon().sqrt();
+
+        let (ar, ai) = self.poly_a.imaginary_axis_parts();
+        let (br, bi) = self.poly_b.imaginary_axis_parts();
+
+        let cross = &ar * &bi - &ai * &br;
+
+        let mut crossings = Vec::new();
+
+        for omega in cross.find_real_roots(F::epsilon()) {
+            if omega < -tol {
+                continue;
+            }
+            let omega = omega.abs();
+
+            let point = Complex::new(F::zero(), omega);
+
+            if let Some(gain) = self.locus_gain(point) {
+                crossings.push(AxisCrossing { point, omega, gain });
+            }
+        }
+
+        crossings.sort_by(|a, b| a.gain.partial_cmp(&b.gain).unwrap());
+
+        let domain = Domain::Continuous;
+        let stable_gains = self.stable_intervals(crossings.iter().map(|x| x.gain), domain);
+
+        StabilityCrossings {
+            domain,
+            crossings,
+            stable_gains,
+        }
+    }
+
+    /// Computes where the branches cross the unit circle, and the resulting stable gains
+    ///
+    /// The crossing frequency is `θ / T`, using the sample time of the locus
+    /// domain, or `T = 1` for a continuous-time locus
+    #[must_use]
+    pub fn unit_circle_crossings(&self) -> StabilityCrossings<F> {
+        let tol = F::epsilon().sqrt();
+
+        let sample_time = match self.domain {
+            Domain::Discrete { sample_time } => sample_time,
+            Domain::Continuous => F::one(),
+        };
+
+        let crossings = self
+            .intersections(&Curve::Circle {
+                center: Complex::zero(),
+                radius: F::one(),
+            })
+            .into_iter()
+            .filter(|x| x.point.im >= -tol)
+            .map(|x| AxisCrossing {
+                point: x.point,
+                omega: x.point.im.abs().atan2(x.point.re) / sample_time,
+                gain: x.gain,
+            })
+            .collect::<Vec<_>>();
+
+        let domain = Domain::Discrete { sample_time };
+        let stable_gains = self.stable_intervals(crossings.iter().map(|x| x.gain), domain);
+
+        StabilityCrossings {
+            domain,
+            crossings,
+            stable_gains,
+        }
+    }
+
+    /// Computes the stability boundary crossings for the domain of the locus
+    #[must_use]
+    pub fn stability_crossings(&self) -> StabilityCrossings<F> {
+        match self.domain {
+            Domain::Continuous => self.imaginary_axis_crossings(),
+            Domain::Discrete { .. } => self.unit_circle_crossings(),
+        }
+    }
+
+    /// Checks whether every root of `A + k B` lies strictly inside the stable region of
+    /// the locus domain
+    #[must_use]
+    pub fn is_stable_at(&self, gain: F) -> bool {
+        self.is_stable_in(gain, self.domain)
+    }
+
+    fn is_stable_in(&self, gain: F, domain: Domain<F>) -> bool {
+        let poly = self.characteristic(gain);
+
+        // Every point of the plane is a root when `A + k B` vanishes
+        if poly.is_zero() {
+            return false;
+        }
+
+        let mut roots = vec![Complex::zero(); poly.order()];
+        poly.find_roots(&mut roots, F::epsilon());
+
+        roots.iter().all(|&x| domain.is_stable(x))
+    }
+
+    /// Splits `[0, ∞)` at the critical gains and keeps the stable pieces
+    ///
+    /// Stability can only change at a critical gain, either a boundary crossing or
+    /// the gain where the order of `A + k B` drops and a branch goes through
+    /// infinity, so testing a single gain strictly inside each piece is enough
+    fn stable_intervals(
+        &self,
+        critical: impl Iterator<Item = F>,
+        domain: Domain<F>,
+    ) -> Vec<GainInterval<F>> {
+        let two = F::from(2).unwrap();
+
+        let mut critical = critical
+            .chain(
+                self.cancellation_gain()
+                    .ok()
+                    .flatten()
+                    .filter(|x| x.is_finite()),
+            )
+            .collect::<Vec<_>>();
+        critical.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut bounds = vec![F::zero()];
+        for gain in critical {
+            if gain > *bounds.last().unwrap() {
+                bounds.push(gain);
+            }
+        }
+        bounds.push(F::infinity());
+
+        let mut out: Vec<GainInterval<F>> = Vec::new();
+
+        for w in bounds.windows(2) {
+            let (min, max) = (w[0], w[1]);
+
+            let test = if max.is_infinite() {
+                if min.is_zero() {
+                    F::one()
+                } else {
+                    min * two
+                }
+            } else {
+                (min + max) / two
+            };
+
+            if !self.is_stable_in(test, domain) {
+                continue;
+            }
+
+            match out.last_mut() {
+                Some(last) if last.max == min => last.max = max,
+                _ => out.push(GainInterval { min, max }),
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::Polynomial;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn third_order_crossings() {
+        // 1 / (s (s + 1) (s + 2)) crosses at ω = √2, k = 6
+        let a = Polynomial::new(vec![0.0, 2.0, 3.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+
+        let rl = RootLocus::new(a, b).unwrap();
+        let res = rl.imaginary_axis_crossings();
+
+        let last = res.crossings.last().unwrap();
+        assert_abs_diff_eq!(last.omega, 2f64.sqrt(), epsilon = 1e-9);
+        assert_abs_diff_eq!(last.gain, 6.0, epsilon = 1e-9);
+
+        assert_eq!(res.stable_gains.len(), 1);
+        assert_abs_diff_eq!(res.stable_gains[0].min, 0.0);
+        assert_abs_diff_eq!(res.stable_gains[0].max, 6.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn conditionally_stable() {
+        // (s + 1)² / s³ is unstable for small gains and stable above k = 0.5
+        let a = Polynomial::new(vec![0.0, 0.0, 0.0, 1.0]);
+        let b = Polynomial::new(vec![1.0, 2.0, 1.0]);
+
+        let rl = RootLocus::new(a, b).unwrap();
+        let res = rl.imaginary_axis_crossings();
+
+        assert_eq!(res.stable_gains.len(), 1);
+        assert_abs_diff_eq!(res.stable_gains[0].min, 0.5, epsilon = 1e-9);
+        assert!(res.stable_gains[0].max.is_infinite());
+    }
+
+    #[test]
+    fn unit_circle() {
+        // 1 / ((z - 1) (z - 0.5)), the closed-loop pair has |z|² = 0.5 + k
+        let a = Polynomial::new(vec![0.5, -1.5, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+
+        let rl = RootLocus::new(a, b)
+            .unwrap()
+            .with_domain(Domain::Discrete { sample_time: 0.1 });
+        let res = rl.stability_crossings();
+
+        assert_eq!(res.domain, Domain::Discrete { sample_time: 0.1 });
+
+        let last = res.crossings.last().unwrap();
+        assert_abs_diff_eq!(last.gain, 0.5, epsilon = 1e-9);
+        assert_abs_diff_eq!(last.point.re, 0.75, epsilon = 1e-9);
+        assert_abs_diff_eq!(last.omega, last.angle() / 0.1, epsilon = 1e-9);
+
+        assert_eq!(res.stable_gains.len(), 1);
+        assert_abs_diff_eq!(res.stable_gains[0].max, 0.5, epsilon = 1e-9);
+    }
+}
@@ -1,10 +1,15 @@
 use std::fmt::Debug;
 
 use super::Polynomial;
+use crate::error::{Error, Result};
 use approx::{AbsDiffEq, RelativeEq};
 use num::{Complex, Float, One, Zero};
 use rand::{distributions::Standard, prelude::*};
 
+/// Maximum number of Newton steps used to polish the real roots
+const NEWTON_STEPS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PolynomialRoot<F> {
     RealSingle(F),
     ComplexPair(Complex<F>),
@@ -22,6 +27,14 @@ pub trait RootFinding<F: Float> {
     /// start guesses inside that region
     fn find_roots(&self, output: &mut [Complex<F>], prec: F) -> usize;
 
+    /// Same as `RootFinding::find_roots`, but checks the polynomial and the result
+    ///
+    /// # Errors
+    ///
+    /// Fails on an empty, zero or non-finite polynomial, and with
+    /// `Error::SolverDivergence` when the solver returns non-finite roots
+    fn try_find_roots(&self, prec: F) -> Result<Vec<Complex<F>>>;
+
     /// Implementation of the Alberth's method
     /// Link to Wikipedia page [here](https://en.wikipedia.org/wiki/Aberth_method)
     ///
@@ -43,6 +56,14 @@ pub trait RootFinding<F: Float> {
     where
         Standard: Distribution<F>;
 
+    /// Finds the distinct real roots of the polynomial, sorted in increasing order
+    ///
+    /// Multiple roots, including a multiple root at zero, are returned once. Roots
+    /// at zero are factored out exactly, the others are found with
+    /// `RootFinding::find_roots`, kept when their imaginary part is negligible
+    /// and polished with a few Newton steps on the real polynomial
+    fn find_real_roots(&self, prec: F) -> Vec<F>;
+
     /// Determines lower and upper bounds for the module of the polynomial roots
     ///
     /// Time complexity: same as `determine_max_bound`, which is called twice
@@ -89,6 +110,24 @@ impl<F: Float + Debug> RootFinding<F> for Polynomial<F> {
         self.find_roots_from(output, prec)
     }
 
+    fn try_find_roots(&self, prec: F) -> Result<Vec<Complex<F>>> {
+        if self.is_zero() {
+            return Err(Error::EmptyPolynomial);
+        }
+        if !self.is_finite() {
+            return Err(Error::NonFiniteCoefficients);
+        }
+
+        let mut out = vec![Complex::zero(); self.order()];
+        self.find_roots(&mut out, prec);
+
+        if out.iter().all(|x| x.re.is_finite() && x.im.is_finite()) {
+            Ok(out)
+        } else {
+            Err(Error::SolverDivergence)
+        }
+    }
+
     fn find_roots_from_rand<R>(
         &self,
         initial_guess: &mut [Complex<F>],
@@ -146,10 +185,10 @@ impl<F: Float + Debug> RootFinding<F> for Polynomial<F> {
 
                 #[cfg(test)]
                 {
-                    println!("{:?} : {:?}", a, b);
+                    println!("{a:?} : {b:?}");
                     println!("1/b = {:?}", Complex::<F>::one() / b);
                     println!("1/b = {:?}", a * (Complex::<F>::one() / b));
-                    println!("a/b = {:?}", frac);
+                    println!("a/b = {frac:?}");
                 }
 
                 *off = frac / (Complex::<F>::one() - frac * sum);
@@ -162,7 +201,7 @@ impl<F: Float + Debug> RootFinding<F> for Polynomial<F> {
 
             #[cfg(test)]
             {
-                println!("{:?}", offsets);
+                println!("{offsets:?}");
             }
 
             guesses
@@ -174,6 +213,82 @@ impl<F: Float + Debug> RootFinding<F> for Polynomial<F> {
         count
     }
 
+    fn find_real_roots(&self, prec: F) -> Vec<F> {
+        let terms = self.get_terms();
+        let zeros = terms.iter().take_while(|x| x.is_zero()).count();
+
+        if zeros == terms.len() {
+            return Vec::new();
+        }
+
+        let mut out = if zeros > 0 {
+            vec![F::zero()]
+        } else {
+            Vec::new()
+        };
+
+        let rest = Self::new(terms[zeros..].to_vec());
+        if rest.order() > 0 {
+            let mut roots = vec![Complex::zero(); rest.order()];
+            rest.find_roots(&mut roots, prec);
+
+            let tol = F::epsilon().sqrt();
+            let derivative = rest.derivative();
+
+            // A multiple root only comes back to about `eps^(1 / multiplicity)`
+            let loose = tol.sqrt();
+
+            for root in roots {
+                if root.re.is_nan()
+                    || root.im.is_nan()
+                    || root.im.abs() > loose * (F::one() + root.re.abs())
+                {
+                    continue;
+                }
+
+                let mut x = root.re;
+                for _ in 0..NEWTON_STEPS {
+                    let step = rest.eval(x) / derivative.eval(x);
+                    if !step.is_finite() {
+                        break;
+                    }
+                    x = x - step;
+                    if step.abs() <= F::epsilon() * (F::one() + x.abs()) {
+                        break;
+                    }
+                }
+
+                // Near a multiple root the steps may wander off instead of converging
+                if rest.eval(x).abs() > rest.eval(root.re).abs() {
+                    x = root.re;
+                }
+
+                // Tells a split multiple root from a complex pair close to the real axis
+                let scale = rest
+                    .get_terms()
+                    .iter()
+                    .rev()
+                    .fold(F::zero(), |acc, &a| acc * x.abs() + a.abs());
+                if root.im.abs() > tol * (F::one() + root.re.abs())
+                    && rest.eval(x).abs() > tol * scale
+                {
+                    continue;
+                }
+
+                // Double roots may come back as a pair with a tiny imaginary part
+                if !out
+                    .iter()
+                    .any(|&y: &F| (x - y).abs() <= tol * (F::one() + x.abs()))
+                {
+                    out.push(x);
+                }
+            }
+        }
+
+        out.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        out
+    }
+
     fn determine_roots_bounds(&self) -> (F, F) {
         let terms = self.get_terms();
         let upper = Self::determine_max_bound(terms);
@@ -211,6 +326,7 @@ impl<F: AbsDiffEq<Epsilon = F> + RelativeEq + Float> RelativeEq for Root<F> {
 }
 
 #[cfg(test)]
+#[allow(clippy::unreadable_literal, clippy::excessive_precision)]
 mod tests {
     use approx::assert_abs_diff_eq;
     use num::complex::{Complex32, Complex64};
@@ -270,6 +386,50 @@ mod tests {
         });
     }
 
+    #[test]
+    fn try_find_roots() {
+        // (x - 1) (x + 2)
+        let roots = Polynomial::new(vec![-2.0, 1.0, 1.0])
+            .try_find_roots(1e-12)
+            .unwrap();
+        assert_eq!(roots.len(), 2);
+
+        assert_eq!(
+            Polynomial::new(vec![0.0]).try_find_roots(1e-12),
+            Err(Error::EmptyPolynomial)
+        );
+        assert_eq!(
+            Polynomial::new(vec![1.0, f64::INFINITY]).try_find_roots(1e-12),
+            Err(Error::NonFiniteCoefficients)
+        );
+    }
+
+    #[test]
+    fn find_real_roots() {
+        // x (x - 1) (x - 2) (x² + 1)
+        let poly = Polynomial::new(vec![0.0, 2.0, -3.0, 3.0, -3.0, 1.0]);
+
+        let roots = poly.find_real_roots(1e-12);
+
+        assert_eq!(roots.len(), 3);
+        for (a, b) in roots.into_iter().zip([0.0, 1.0, 2.0]) {
+            assert_abs_diff_eq!(a, b, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn find_real_roots_multiple() {
+        // x² (x - 1)² (x + 2), every root is returned once
+        let poly = Polynomial::new(vec![0.0, 0.0, 2.0, -3.0, 0.0, 1.0]);
+
+        let roots = poly.find_real_roots(1e-12);
+
+        assert_eq!(roots.len(), 3);
+        for (a, b) in roots.into_iter().zip([-2.0, 0.0, 1.0]) {
+            assert_abs_diff_eq!(a, b, epsilon = 1e-6);
+        }
+    }
+
     #[test]
     fn find_roots_1() {
         // 1 x^8 + 16.214523 x^7 + 99.22398 x^6 + 315.04803 x^5 + 580.4285 x^4 +
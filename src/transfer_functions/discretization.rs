@@ -0,0 +1,402 @@
+use std::fmt::Debug;
+
+use num::{Complex, Float, One};
+
+use super::{real_polynomial, state_space::StateSpace, TransferFunction};
+use crate::{
+    error::{Error, Result},
+    matrix::Matrix,
+    polynomials::Polynomial,
+};
+
+/// How a transfer function is moved between continuous and discrete time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Discretization<F> {
+    /// Zero-order hold on the input, exact for piecewise constant inputs
+    ZeroOrderHold,
+    /// Bilinear transform `s = c (z - 1) / (z + 1)`, with `c = 2 / T`, or
+    /// `c = ω / tan(ωT / 2)` to keep the response at the prewarping frequency `ω`
+    Tustin { prewarp: Option<F> },
+    /// Maps the poles and zeros with `z = e^(sT)` and the zeros at infinity to
+    /// `z = -1`, then matches the low frequency gain
+    MatchedPoleZero,
+}
+
+/// Substitutes `x = f / g` in `P` and multiplies by `g^order`, with `order` at
+/// least the order of `P`
+fn substitute<F: Float>(
+    poly: &Polynomial<F>,
+    f: &Polynomial<F>,
+    g: &Polynomial<F>,
+    order: usize,
+) -> Polynomial<F> {
+    let powers = |x: &Polynomial<F>| {
+        let mut out = vec![Polynomial::new(vec![F::one()])];
+        for i in 0..order {
+            out.push(&out[i] * x);
+        }
+        out
+    };
+    let (f, g) = (powers(f), powers(g));
+
+    poly.get_terms()
+        .iter()
+        .enumerate()
+        .fold(Polynomial::new(vec![F::zero()]), |acc, (i, &p)| {
+            acc + (&f[i] * &g[order - i]) * p
+        })
+}
+
+/// Frequency `ω` of the point `e^(jωT)` of the unit circle whose w-plane image is `jν`
+pub(super) fn w_plane_frequency<F: Float>(nu: F, sample_time: F) -> F {
+    (nu.atan() + nu.atan()) / sample_time
+}
+
+/// The constant of the bilinear transform `s = c (z - 1) / (z + 1)`
+fn tustin_constant<F: Float>(sample_time: F, prewarp: Option<F>) -> Result<F> {
+    let two = F::from(2).unwrap();
+
+    prewarp.map_or(Ok(two / sample_time), |w| {
+        if w > F::zero() && w * sample_time < F::from(std::f64::consts::PI).unwrap() {
+            Ok(w / (w * sample_time / two).tan())
+        } else {
+            Err(Error::InvalidParameter(
+                "the prewarping frequency must be between 0 and the Nyquist frequency",
+            ))
+        }
+    })
+}
+
+impl<F: Float + Debug> TransferFunction<F> {
+    /// Converts a continuous-time transfer function to discrete time
+    ///
+    /// # Errors
+    ///
+    /// `Error::InvalidParameter` if the sample time is not positive or the
+    /// prewarping frequency is not between 0 and the Nyquist frequency,
+    /// `Error::UnsupportedConversion` if the transfer function is already discrete,
+    /// and `Error::ImproperSystem` for an improper system with the zero-order hold
+    /// or the matched pole-zero method
+    pub fn c2d(&self, sample_time: F, method: Discretization<F>) -> Result<Self> {
+        if sample_time.is_nan() || sample_time <= F::zero() {
+            return Err(Error::InvalidParameter("the sample time must be positive"));
+        }
+
+        if self.is_discrete() {
+            return Err(Error::UnsupportedConversion);
+        }
+
+        let out = match method {
+            Discretization::ZeroOrderHold => self.zero_order_hold(sample_time)?,
+            Discretization::Tustin { prewarp } => {
+                let c = tustin_constant(sample_time, prewarp)?;
+                self.bilinear(
+                    &Polynomial::new(vec![-c, c]),
+                    &Polynomial::new(vec![F::one(), F::one()]),
+                )?
+            }
+            Discretization::MatchedPoleZero => self.matched_c2d(sample_time)?,
+        };
+
+        Ok(out.with_sample_time(Some(sample_time)))
+    }
+
+    /// Converts a discrete-time transfer function back to continuous time,
+    /// inverting the Tustin or matched pole-zero methods
+    ///
+    /// # Errors
+    ///
+    /// `Error::UnsupportedConversion` if the transfer function is not discrete, for the
+    /// zero-order hold, and for the matched pole-zero method when a pole or zero is at `z = 0` or on the
+    /// negative real axis (other than zeros at `z = -1`), where `ln z` has no real
+    /// counterpart. `Error::InvalidParameter` if the prewarping frequency is not
+    /// between 0 and the Nyquist frequency
+    pub fn d2c(&self, method: Discretization<F>) -> Result<Self> {
+        let sample_time = self.sample_time.ok_or(Error::UnsupportedConversion)?;
+
+        let out = match method {
+            Discretization::ZeroOrderHold => return Err(Error::UnsupportedConversion),
+            Discretization::Tustin { prewarp } => {
+                // z = (c + s) / (c - s)
+                let c = tustin_constant(sample_time, prewarp)?;
+                self.bilinear(
+                    &Polynomial::new(vec![c, F::one()]),
+                    &Polynomial::new(vec![c, -F::one()]),
+                )?
+            }
+            Discretization::MatchedPoleZero => self.matched_d2c(sample_time)?,
+        };
+
+        Ok(out.with_sample_time(None))
+    }
+
+    /// The w-plane image `G((1 + w) / (1 - w))` of a discrete transfer function
+    ///
+    /// It maps the unit circle `e^(jωT)` to the imaginary axis `j tan(ωT / 2)` and
+    /// its inside to the left half plane, so the continuous-time tools apply to
+    /// discrete loops. `z = -1` is sent to infinity.
+    pub(super) fn w_plane(&self) -> Self {
+        let order = self.numerator.order().max(self.denominator.order());
+        let f = Polynomial::new(vec![F::one(), F::one()]);
+        let g = Polynomial::new(vec![F::one(), -F::one()]);
+
+        Self {
+            numerator: substitute(&self.numerator, &f, &g, order),
+            denominator: substitute(&self.denominator, &f, &g, order),
+            sample_time: None,
+        }
+    }
+
+    /// Substitutes `f / g` for the variable in both polynomials
+    fn bilinear(&self, f: &Polynomial<F>, g: &Polynomial<F>) -> Result<Self> {
+        let order = self.numerator.order().max(self.denominator.order());
+
+        Self::new(
+            substitute(&self.numerator, f, g, order),
+            substitute(&self.denominator, f, g, order),
+        )
+    }
+
+    /// `x(k+1) = e^(AT) x(k) + ∫ e^(Aτ) dτ B u(k)`, where both come from the
+    /// exponential of `[[A, B], [0, 0]] T`
+    fn zero_order_hold(&self, sample_time: F) -> Result<Self> {
+        let ss = StateSpace::controllable_canonical(self)?;
+        let n = ss.order();
+
+        let mut augmented = Matrix::zeros(n + 1, n + 1);
+        for i in 0..n {
+            for j in 0..n {
+                augmented[(i, j)] = ss.get_a()[(i, j)] * sample_time;
+            }
+            augmented[(i, n)] = ss.get_b()[(i, 0)] * sample_time;
+        }
+        let exp = augmented.exp();
+
+        let mut a = Matrix::zeros(n, n);
+        let mut b = Matrix::zeros(n, 1);
+        for i in 0..n {
+            for j in 0..n {
+                a[(i, j)] = exp[(i, j)];
+            }
+            b[(i, 0)] = exp[(i, n)];
+        }
+
+        StateSpace::new(a, b, ss.get_c().clone(), ss.get_d())?.to_transfer_function()
+    }
+
+    fn matched_c2d(&self, sample_time: F) -> Result<Self> {
+        if !self.is_proper() {
+            return Err(Error::ImproperSystem {
+                numerator_order: self.numerator.order(),
+                denominator_order: self.denominator.order(),
+            });
+        }
+        if self.numerator.is_zero() {
+            return Ok(self.clone());
+        }
+
+        // The poles and zeros at s = 0 map exactly to z = 1
+        let (num, zeros_at_origin) = self.numerator.deflate(F::zero());
+        let (den, poles_at_origin) = self.denominator.deflate(F::zero());
+
+        let map = |x: Complex<F>| (x * sample_time).exp();
+        let zeros = Self::roots_of(&num)
+            .into_iter()
+            .map(map)
+            .collect::<Vec<_>>();
+        let poles = Self::roots_of(&den)
+            .into_iter()
+            .map(map)
+            .collect::<Vec<_>>();
+
+        let at_minus_one = vec![-Complex::<F>::one(); self.relative_degree().unsigned_abs()];
+        let mut numerator = &real_polynomial(&zeros) * &real_polynomial(&at_minus_one);
+        let mut denominator = real_polynomial(&poles);
+
+        let gain = (num.eval(F::zero()) / den.eval(F::zero()))
+            / (numerator.eval(F::one()) / denominator.eval(F::one()));
+        numerator = numerator * gain;
+
+        // s matches (z - 1) / T at low frequencies
+        let integrator = Polynomial::new(vec![-sample_time.recip(), sample_time.recip()]);
+        for _ in 0..zeros_at_origin {
+            numerator = &numerator * &integrator;
+        }
+        for _ in 0..poles_at_origin {
+            denominator = &denominator * &integrator;
+        }
+
+        Self::new(numerator, denominator)
+    }
+
+    fn matched_d2c(&self, sample_time: F) -> Result<Self> {
+        if self.numerator.is_zero() {
+            return Ok(self.clone());
+        }
+
+        let tol = F::epsilon().sqrt();
+
+        // The poles and zeros at z = 1 map exactly to s = 0
+        let (num, zeros_at_one) = self.numerator.deflate(F::one());
+        let (den, poles_at_one) = self.denominator.deflate(F::one());
+        let low_frequency_gain = num.eval(F::one()) / den.eval(F::one());
+
+        // The zeros at z = -1 go back to infinity
+        let (num, _) = num.deflate(-F::one());
+
+        let map = |x: Complex<F>| {
+            let on_negative_axis = x.re <= F::zero() && x.im.abs() <= tol * (F::one() + x.norm());
+            if on_negative_axis {
+                Err(Error::UnsupportedConversion)
+            } else {
+                Ok(x.ln() / sample_time)
+            }
+        };
+        let zeros = Self::roots_of(&num)
+            .into_iter()
+            .map(map)
+            .collect::<Result<Vec<_>>>()?;
+        let poles = Self::roots_of(&den)
+            .into_iter()
+            .map(map)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut numerator = real_polynomial(&zeros);
+        let mut denominator = real_polynomial(&poles);
+
+        let gain = low_frequency_gain / (numerator.eval(F::zero()) / denominator.eval(F::zero()));
+        numerator = numerator * gain;
+
+        // (z - 1) matches s T at low frequencies
+        let integrator = Polynomial::new(vec![F::zero(), sample_time]);
+        for _ in 0..zeros_at_one {
+            numerator = &numerator * &integrator;
+        }
+        for _ in 0..poles_at_one {
+            denominator = &denominator * &integrator;
+        }
+
+        Self::new(numerator, denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn assert_same_response(a: &TransferFunction<f64>, b: &TransferFunction<f64>) {
+        for x in [
+            Complex::new(0.3, 0.7),
+            Complex::new(-2.0, 1.0),
+            Complex::new(5.0, 0.0),
+        ] {
+            assert_abs_diff_eq!((a.eval(x) - b.eval(x)).norm(), 0.0, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn zero_order_hold() {
+        // 1 / (s + 1) becomes (1 - e^-T) / (z - e^-T)
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![1.0, 1.0]).unwrap();
+        let t = 0.1;
+        let a = (-t).exp();
+
+        let d = g.c2d(t, Discretization::ZeroOrderHold).unwrap();
+        assert_eq!(d.get_sample_time(), Some(t));
+        let expected = TransferFunction::from_coefficients(vec![1.0 - a], vec![-a, 1.0]).unwrap();
+        assert_same_response(&d, &expected);
+        assert_abs_diff_eq!(d.dc_gain(), 1.0, epsilon = 1e-12);
+
+        // 1 / s² becomes T² (z + 1) / (2 (z - 1)²)
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![0.0, 0.0, 1.0]).unwrap();
+        let d = g.c2d(t, Discretization::ZeroOrderHold).unwrap();
+        let expected = TransferFunction::from_coefficients(
+            vec![t * t / 2.0, t * t / 2.0],
+            vec![1.0, -2.0, 1.0],
+        )
+        .unwrap();
+        assert_same_response(&d, &expected);
+        assert!(d.dc_gain().is_infinite());
+
+        assert_eq!(
+            d.d2c(Discretization::ZeroOrderHold),
+            Err(Error::UnsupportedConversion)
+        );
+
+        // Converting twice in the same direction is rejected instead of panicking
+        assert_eq!(
+            d.c2d(t, Discretization::ZeroOrderHold),
+            Err(Error::UnsupportedConversion)
+        );
+        assert_eq!(
+            g.d2c(Discretization::Tustin { prewarp: None }),
+            Err(Error::UnsupportedConversion)
+        );
+
+        // So are a non-positive sample time and a prewarping frequency above Nyquist
+        assert!(matches!(
+            g.c2d(0.0, Discretization::ZeroOrderHold),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            g.c2d(
+                t,
+                Discretization::Tustin {
+                    prewarp: Some(100.0)
+                }
+            ),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn tustin() {
+        // 1 / (s + 1) with T = 0.1 becomes (z + 1) / (21z - 19)
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![1.0, 1.0]).unwrap();
+
+        let d = g
+            .c2d(0.1, Discretization::Tustin { prewarp: None })
+            .unwrap();
+        let expected =
+            TransferFunction::from_coefficients(vec![1.0, 1.0], vec![-19.0, 21.0]).unwrap();
+        assert_same_response(&d, &expected);
+        assert_same_response(
+            &d.d2c(Discretization::Tustin { prewarp: None }).unwrap(),
+            &g,
+        );
+
+        // The response at the prewarping frequency is kept
+        let w = 10.0;
+        let method = Discretization::Tustin { prewarp: Some(w) };
+        let d = g.c2d(0.1, method).unwrap();
+        let continuous = g.freq_response(&[w])[0];
+        let discrete = d.freq_response(&[w])[0];
+        assert_abs_diff_eq!((continuous - discrete).norm(), 0.0, epsilon = 1e-12);
+        assert_same_response(&d.d2c(method).unwrap(), &g);
+    }
+
+    #[test]
+    fn matched_pole_zero() {
+        // 1 / (s (s + 2)) becomes K (z + 1)² / ((z - 1) (z - e^-2T))
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![0.0, 2.0, 1.0]).unwrap();
+        let t = 0.5;
+
+        let d = g.c2d(t, Discretization::MatchedPoleZero).unwrap();
+        let poles = d.poles();
+        for p in [1.0, (-2.0 * t).exp()] {
+            assert!(poles
+                .iter()
+                .any(|x| (x.re - p).abs() < 1e-9 && x.im.abs() < 1e-9));
+        }
+        assert_eq!(d.get_numerator().order(), 2);
+
+        // Both velocity constants agree: s G(s) at 0 and (z - 1) / T G(z) at 1
+        let kv = d.get_numerator().eval(1.0) / (d.get_denominator().deflate(1.0).0.eval(1.0) * t);
+        assert_abs_diff_eq!(kv, 0.5, epsilon = 1e-12);
+
+        let back = d.d2c(Discretization::MatchedPoleZero).unwrap();
+        assert!(!back.is_discrete());
+        assert_same_response(&back, &g);
+    }
+}
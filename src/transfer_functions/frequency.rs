@@ -0,0 +1,187 @@
+use std::fmt::Debug;
+
+use num::{Complex, Float};
+
+use super::TransferFunction;
+
+/// A point of the Bode plots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodePoint<F> {
+    /// Angular frequency, in rad/s
+    pub omega: F,
+    /// `20 log10 |G(jω)|`
+    pub magnitude_db: F,
+    /// Unwrapped `arg G(jω)`, in degrees
+    pub phase_deg: F,
+}
+
+/// `points_per_decade` frequencies per decade from `10^start_decade` to `10^end_decade`, both included
+#[must_use]
+pub fn logspace<F: Float>(start_decade: i32, end_decade: i32, points_per_decade: usize) -> Vec<F> {
+    assert!(start_decade <= end_decade, "the decades must be ordered");
+    assert!(
+        points_per_decade > 0,
+        "at least one point per decade is needed"
+    );
+
+    let steps = (end_decade - start_decade).unsigned_abs() as usize * points_per_decade;
+    let ten = F::from(10).unwrap();
+    let start = F::from(start_decade).unwrap();
+    let step = F::one() / F::from(points_per_decade).unwrap();
+
+    (0..=steps)
+        .map(|i| ten.powf(start + step * F::from(i).unwrap()))
+        .collect()
+}
+
+/// Adds multiples of 360° to each phase so that consecutive phases differ by at most 180°
+fn unwrap_phase<F: Float>(phases: &mut [F]) {
+    let turn = F::from(360).unwrap();
+    let half = F::from(180).unwrap();
+
+    let mut offset = F::zero();
+    for i in 1..phases.len() {
+        let prev = phases[i - 1];
+        let mut curr = phases[i] + offset;
+
+        while curr - prev > half {
+            curr = curr - turn;
+            offset = offset - turn;
+        }
+        while curr - prev < -half {
+            curr = curr + turn;
+            offset = offset + turn;
+        }
+
+        phases[i] = curr;
+    }
+}
+
+impl<F: Float + Debug> TransferFunction<F> {
+    /// Evaluates `G(jω)` at every frequency, or `G(e^(jωT))` in discrete time
+    #[must_use]
+    pub fn freq_response(&self, omegas: &[F]) -> Vec<Complex<F>> {
+        let t = self.sample_time.unwrap_or_else(F::one);
+
+        omegas
+            .iter()
+            .map(|&w| {
+                let s = Complex::new(F::zero(), w);
+                self.eval(if self.is_discrete() { (s * t).exp() } else { s })
+            })
+            .collect()
+    }
+
+    /// The decades around the poles and zeros, with one more decade on each side
+    ///
+    /// The corner frequencies are rounded to the nearest decade, which keeps
+    /// them at least half a decade inside the range.
+    ///
+    /// Poles and zeros at the origin are left out. Without any other, the range
+    /// is from 0.1 to 10 rad/s. In discrete time the roots are mapped to the
+    /// s-plane with `s = ln(z) / T`.
+    #[must_use]
+    pub fn decade_range(&self) -> (i32, i32) {
+        let (min, max) = self
+            .poles()
+            .into_iter()
+            .chain(self.zeros())
+            .map(|x| self.sample_time.map_or(x, |t| x.ln() / t))
+            .map(Complex::norm)
+            .filter(|x| x.is_finite() && !x.is_zero())
+            .map(Float::log10)
+            .fold((F::infinity(), F::neg_infinity()), |(min, max), x| {
+                (min.min(x), max.max(x))
+            });
+
+        if min > max {
+            return (-1, 1);
+        }
+
+        (
+            min.round().to_i32().unwrap() - 1,
+            max.round().to_i32().unwrap() + 1,
+        )
+    }
+
+    /// Computes the Bode plots at the given increasing frequencies
+    #[must_use]
+    pub fn bode_at(&self, omegas: &[F]) -> Vec<BodePoint<F>> {
+        let twenty = F::from(20).unwrap();
+        let response = self.freq_response(omegas);
+
+        let mut phases = response
+            .iter()
+            .map(|x| x.arg().to_degrees())
+            .collect::<Vec<_>>();
+        unwrap_phase(&mut phases);
+
+        omegas
+            .iter()
+            .zip(response)
+            .zip(phases)
+            .map(|((&omega, g), phase_deg)| BodePoint {
+                omega,
+                magnitude_db: twenty * g.norm().log10(),
+                phase_deg,
+            })
+            .collect()
+    }
+
+    /// Computes the Bode plots over `TransferFunction::decade_range`
+    #[must_use]
+    pub fn bode(&self, points_per_decade: usize) -> Vec<BodePoint<F>> {
+        let (start, end) = self.decade_range();
+        self.bode_at(&logspace(start, end, points_per_decade))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn logspace_decades() {
+        let omegas = logspace::<f64>(-1, 1, 2);
+        let expected = [0.1, 10f64.powf(-0.5), 1.0, 10f64.sqrt(), 10.0];
+
+        assert_eq!(omegas.len(), expected.len());
+        for (w, e) in omegas.iter().zip(expected) {
+            assert_abs_diff_eq!(*w, e, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn first_order() {
+        // 1 / (s + 1)
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![1.0, 1.0]).unwrap();
+
+        let corner = g.bode_at(&[1.0])[0];
+        assert_abs_diff_eq!(corner.magnitude_db, -10.0 * 2f64.log10(), epsilon = 1e-12);
+        assert_abs_diff_eq!(corner.phase_deg, -45.0, epsilon = 1e-12);
+
+        let response = g.freq_response(&[2.0]);
+        assert_abs_diff_eq!(response[0].re, 0.2, epsilon = 1e-12);
+        assert_abs_diff_eq!(response[0].im, -0.4, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn unwrapped_phase() {
+        // 100 / ((s + 1)³ (s + 100)) goes below -180°
+        let g =
+            TransferFunction::from_coefficients(vec![100.0], vec![100.0, 301.0, 303.0, 103.0, 1.0])
+                .unwrap();
+
+        assert_eq!(g.decade_range(), (-1, 3));
+
+        let bode = g.bode(20);
+        assert_abs_diff_eq!(bode[0].omega, 0.1, epsilon = 1e-12);
+        assert_abs_diff_eq!(bode.last().unwrap().omega, 1000.0, epsilon = 1e-9);
+        assert!(bode.windows(2).all(|w| w[1].phase_deg < w[0].phase_deg));
+
+        // Close to -270° from the triple pole and -84° from the pole at 100
+        let last = bode.last().unwrap();
+        assert!(last.phase_deg < -340.0 && last.phase_deg > -360.0);
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt::Debug;
+
+use num::{Complex, Float};
+
+use super::{discretization::w_plane_frequency, TransferFunction};
+use crate::polynomials::{roots::RootFinding, Polynomial};
+
+/// A phase crossover, where `∠G(jω) = -180°`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GainMargin<F> {
+    /// Phase crossover frequency, in rad/s, at most `π / T` in discrete time
+    pub omega: F,
+    /// `1 / |G(jω)|`, the gain in series with `G` that puts a closed-loop pole at `jω`
+    pub gain: F,
+}
+
+impl<F: Float> GainMargin<F> {
+    /// The gain margin in decibels
+    #[must_use]
+    pub fn gain_db(&self) -> F {
+        F::from(20).unwrap() * self.gain.log10()
+    }
+}
+
+/// A gain crossover, where `|G(jω)| = 1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseMargin<F> {
+    /// Gain crossover frequency, in rad/s
+    pub omega: F,
+    /// `180° + ∠G(jω)`, wrapped to `(-180°, 180°]`
+    pub phase_deg: F,
+}
+
+/// Keeps the non-negative roots, sorted and without duplicates
+fn crossover_frequencies<F: Float + Debug>(poly: &Polynomial<F>) -> Vec<F> {
+    let tol = F::epsilon().sqrt();
+
+    let mut out = poly
+        .find_real_roots(F::epsilon())
+        .into_iter()
+        .filter(|&x| x >= -tol)
+        .map(Float::abs)
+        .collect::<Vec<_>>();
+
+    out.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    out.dedup_by(|a, b| (*a - *b).abs() <= tol * (F::one() + b.abs()));
+    out
+}
+
+impl<F: Float + Debug> TransferFunction<F> {
+    /// Computes every phase crossover, sorted by frequency
+    ///
+    /// Writing `N(jω) = Nr(ω) + j Ni(ω)` (and the same for D), `G(jω)` is real
+    /// where `Ni Dr - Nr Di = 0`. The crossovers are the real roots where it is
+    /// also negative. The gains match the imaginary axis crossings of the root locus.
+    ///
+    /// In discrete time the unit circle `e^(jωT)` is searched instead, through the
+    /// imaginary axis of the w-plane, and `z = -1` is checked on its own since the
+    /// w-plane sends it to infinity. The gains match the unit circle crossings.
+    #[must_use]
+    pub fn gain_margins(&self) -> Vec<GainMargin<F>> {
+        if let Some(t) = self.sample_time {
+            return self.discrete_gain_margins(t);
+        }
+
+        let (nr, ni) = self.numerator.imaginary_axis_parts();
+        let (dr, di) = self.denominator.imaginary_axis_parts();
+
+        let imaginary = &ni * &dr - &nr * &di;
+        if imaginary.is_zero() {
+            return Vec::new();
+        }
+
+        crossover_frequencies(&imaginary)
+            .into_iter()
+            .filter_map(|omega| {
+                let g = self.eval(Complex::new(F::zero(), omega));
+                (g.re.is_finite() && g.re < F::zero()).then(|| GainMargin {
+                    omega,
+                    gain: -g.re.recip(),
+                })
+            })
+            .collect()
+    }
+
+    /// Computes every gain crossover, sorted by frequency
+    ///
+    /// `|G(jω)| = 1` where `Nr² + Ni² - Dr² - Di² = 0`, or on the unit circle
+    /// `e^(jωT)` in discrete time
+    #[must_use]
+    pub fn phase_margins(&self) -> Vec<PhaseMargin<F>> {
+        if let Some(t) = self.sample_time {
+            return self
+                .w_plane()
+                .phase_margins()
+                .into_iter()
+                .map(|x| PhaseMargin {
+                    omega: w_plane_frequency(x.omega, t),
+                    ..x
+                })
+                .collect();
+        }
+
+        let (nr, ni) = self.numerator.imaginary_axis_parts();
+        let (dr, di) = self.denominator.imaginary_axis_parts();
+
+        let magnitude = &nr * &nr + &ni * &ni - &dr * &dr - &di * &di;
+        if magnitude.is_zero() {
+            return Vec::new();
+        }
+
+        crossover_frequencies(&magnitude)
+            .into_iter()
+            .filter_map(|omega| {
+                let g = self.eval(Complex::new(F::zero(), omega));
+                g.re.is_finite().then(|| PhaseMargin {
+                    omega,
+                    phase_deg: (-g).arg().to_degrees(),
+                })
+            })
+            .collect()
+    }
+
+    fn discrete_gain_margins(&self, sample_time: F) -> Vec<GainMargin<F>> {
+        let tol = F::epsilon().sqrt();
+
+        let mut out = self
+            .w_plane()
+            .gain_margins()
+            .into_iter()
+            .map(|x| GainMargin {
+                omega: w_plane_frequency(x.omega, sample_time),
+                ..x
+            })
+            .collect::<Vec<_>>();
+
+        // The Nyquist frequency, where G is real
+        let omega = F::from(std::f64::consts::PI).unwrap() / sample_time;
+        let g = self.eval(Complex::from(-F::one()));
+        if g.re.is_finite()
+            && g.re < F::zero()
+            && out
+                .last()
+                .is_none_or(|x| (x.omega - omega).abs() > tol * omega)
+        {
+            out.push(GainMargin {
+                omega,
+                gain: -g.re.recip(),
+            });
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::root_locus::RootLocus;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn matches_critical_gain() {
+        // 1 / (s (s + 1) (s + 2)) crosses -180° at √2 rad/s, where |G| = 1 / 6
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![0.0, 2.0, 3.0, 1.0]).unwrap();
+
+        let margins = g.gain_margins();
+        assert_eq!(margins.len(), 1);
+        assert_abs_diff_eq!(margins[0].omega, 2f64.sqrt(), epsilon = 1e-9);
+        assert_abs_diff_eq!(margins[0].gain, 6.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(margins[0].gain_db(), 20.0 * 6f64.log10(), epsilon = 1e-9);
+
+        let rl = RootLocus::from_open_loop(&g).unwrap();
+        let critical = rl.imaginary_axis_crossings().crossings.last().unwrap().gain;
+        assert_abs_diff_eq!(margins[0].gain, critical, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn phase_margin() {
+        // 2 / (s (s + 1)), with ω² (ω² + 1) = 4 at the crossover
+        let g = TransferFunction::from_coefficients(vec![2.0], vec![0.0, 1.0, 1.0]).unwrap();
+        let omega = ((17f64.sqrt() - 1.0) / 2.0).sqrt();
+
+        let margins = g.phase_margins();
+        assert_eq!(margins.len(), 1);
+        assert_abs_diff_eq!(margins[0].omega, omega, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            margins[0].phase_deg,
+            90.0 - omega.atan().to_degrees(),
+            epsilon = 1e-9
+        );
+
+        // The phase never reaches -180°
+        assert!(g.gain_margins().is_empty());
+    }
+
+    #[test]
+    fn discrete_margins() {
+        // 1 / (z (z - 0.5)) reaches -180° where cos(ωT) = 1/4, with |G| = 1
+        let t = 0.1;
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![0.0, -0.5, 1.0])
+            .unwrap()
+            .with_sample_time(Some(t));
+
+        let margins = g.gain_margins();
+        assert_eq!(margins.len(), 1);
+        assert_abs_diff_eq!(margins[0].omega, 0.25f64.acos() / t, epsilon = 1e-9);
+        assert_abs_diff_eq!(margins[0].gain, 1.0, epsilon = 1e-9);
+
+        let rl = RootLocus::from_open_loop(&g).unwrap();
+        let critical = rl.unit_circle_crossings().crossings.last().unwrap().gain;
+        assert_abs_diff_eq!(margins[0].gain, critical, epsilon = 1e-9);
+
+        let phase = g.phase_margins();
+        assert!(phase.iter().all(|m| {
+            let z = Complex::new(0.0, m.omega * t).exp();
+            (g.eval(z).norm() - 1.0).abs() < 1e-9
+        }));
+
+        // 1 / (z + 0.5) only crosses -180° at the Nyquist frequency, where G = -2
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![0.5, 1.0])
+            .unwrap()
+            .with_sample_time(Some(t));
+
+        let margins = g.gain_margins();
+        assert_eq!(margins.len(), 1);
+        assert_abs_diff_eq!(margins[0].omega, std::f64::consts::PI / t, epsilon = 1e-9);
+        assert_abs_diff_eq!(margins[0].gain, 0.5, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn multiple_crossings() {
+        // 4 (s² + 0.05s + 1) / ((s + 1) (s² + 0.5s + 1)), the notch at 1 rad/s
+        // dips below 0 dB between the low and high frequency crossovers
+        let g = TransferFunction::from_coefficients(vec![4.0, 0.2, 4.0], vec![1.0, 1.5, 1.5, 1.0])
+            .unwrap();
+
+        let margins = g.phase_margins();
+        assert_eq!(margins.len(), 3);
+        assert!(margins.windows(2).all(|w| w[0].omega < w[1].omega));
+        for m in &margins {
+            let g = g.eval(Complex::new(0.0, m.omega));
+            assert_abs_diff_eq!(g.norm(), 1.0, epsilon = 1e-9);
+        }
+    }
+}
@@ -1,9 +1,609 @@
-use num::Float;
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    ops::{Add, Mul, Sub},
+};
 
-use crate::polynomials::Polynomial;
+use num::{Complex, Float, One, Zero};
 
-#[allow(dead_code)]
+use crate::{
+    error::{Error, Result},
+    polynomials::{
+        conv,
+        roots::{PolynomialRoot, RootFinding},
+        Polynomial,
+    },
+};
+
+pub mod discretization;
+pub mod frequency;
+pub mod margins;
+pub mod nyquist;
+pub mod state_space;
+pub mod steady_state;
+pub mod step_metrics;
+pub mod time_response;
+
+/// Sign of the feedback path of a closed loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackSign {
+    /// `G / (1 + G H)`
+    Negative,
+    /// `G / (1 - G H)`
+    Positive,
+}
+
+/// Builds the real polynomial `Π(x - rᵢ)` from roots closed under conjugation
+fn real_polynomial<F: Float>(roots: &[Complex<F>]) -> Polynomial<F> {
+    let mut out = vec![Complex::one()];
+
+    for &root in roots {
+        let mut next = vec![Complex::zero(); out.len() + 1];
+        conv(&out, &[-root, Complex::one()], &mut next);
+        out = next;
+    }
+
+    Polynomial::new(out.iter().map(|x| x.re).collect())
+}
+
+/// A rational transfer function `N(s) / D(s)`, or `N(z) / D(z)` in discrete time
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TransferFunction<F: Float> {
     numerator: Polynomial<F>,
     denominator: Polynomial<F>,
+    /// `None` in continuous time
+    sample_time: Option<F>,
+}
+
+impl<F: Float + Debug> TransferFunction<F> {
+    /// # Errors
+    ///
+    /// `Error::EmptyPolynomial` if the denominator is zero, and
+    /// `Error::NonFiniteCoefficients` if any coefficient is NaN or infinite
+    pub fn new(numerator: Polynomial<F>, denominator: Polynomial<F>) -> Result<Self> {
+        if denominator.is_zero() {
+            return Err(Error::EmptyPolynomial);
+        }
+        if !numerator.is_finite() || !denominator.is_finite() {
+            return Err(Error::NonFiniteCoefficients);
+        }
+
+        Ok(Self {
+            numerator,
+            denominator,
+            sample_time: None,
+        })
+    }
+
+    /// Sets the sample time, which makes the polynomials in `z`, or `None` for continuous time
+    #[must_use]
+    pub fn with_sample_time(mut self, sample_time: Option<F>) -> Self {
+        if let Some(t) = sample_time {
+            assert!(t > F::zero(), "the sample time must be positive");
+        }
+        self.sample_time = sample_time;
+        self
+    }
+
+    /// Builds the transfer function from its coefficients, from the lowest to the highest order
+    ///
+    /// # Errors
+    ///
+    /// Same as `TransferFunction::new`, and `Error::EmptyPolynomial` for empty coefficients
+    pub fn from_coefficients(numerator: Vec<F>, denominator: Vec<F>) -> Result<Self> {
+        Self::new(
+            Polynomial::try_new(numerator)?,
+            Polynomial::try_new(denominator)?,
+        )
+    }
+
+    /// Builds `k Π(s - zᵢ) / Π(s - pᵢ)`
+    ///
+    /// # Errors
+    ///
+    /// Same as `TransferFunction::new`
+    pub fn from_zpk(
+        zeros: &[PolynomialRoot<F>],
+        poles: &[PolynomialRoot<F>],
+        gain: F,
+    ) -> Result<Self> {
+        Self::new(
+            Polynomial::from_roots(gain, zeros),
+            Polynomial::from_roots(F::one(), poles),
+        )
+    }
+
+    /// Builds `N / D` after cancelling the poles and zeros that both share
+    ///
+    /// Common factors of `s^k` are removed exactly. Every other zero is cancelled
+    /// with the nearest pole not cancelled yet, if it is within a relative
+    /// tolerance, and both polynomials are then rebuilt from their remaining roots.
+    /// The tolerance is loose since a root of multiplicity `m` is only found to
+    /// about `ε^(1/m)`.
+    fn reduced(
+        numerator: Polynomial<F>,
+        denominator: Polynomial<F>,
+        sample_time: Option<F>,
+    ) -> Self {
+        let lowest = |p: &Polynomial<F>| {
+            p.get_terms()
+                .iter()
+                .position(|x| !x.is_zero())
+                .unwrap_or(usize::MAX)
+        };
+        let shift = lowest(&numerator).min(lowest(&denominator));
+
+        let (numerator, denominator) = if shift == 0 || numerator.is_zero() {
+            (numerator, denominator)
+        } else {
+            (
+                Polynomial::new(numerator.get_terms()[shift..].to_vec()),
+                Polynomial::new(denominator.get_terms()[shift..].to_vec()),
+            )
+        };
+
+        let unchanged = |numerator, denominator| Self {
+            numerator,
+            denominator,
+            sample_time,
+        };
+
+        if numerator.order() == 0 || denominator.order() == 0 {
+            return unchanged(numerator, denominator);
+        }
+
+        let tol = F::epsilon().sqrt().sqrt();
+        let mut zeros = Self::roots_of(&numerator);
+        let mut poles = Self::roots_of(&denominator);
+        let count = zeros.len();
+
+        zeros.retain(|&zero| {
+            let nearest = poles
+                .iter()
+                .enumerate()
+                .map(|(i, &pole)| (i, (zero - pole).norm()))
+                .filter(|&(_, distance)| distance <= tol * (F::one() + zero.norm()))
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+            match nearest {
+                Some((i, _)) => {
+                    poles.swap_remove(i);
+                    false
+                }
+                None => true,
+            }
+        });
+
+        if zeros.len() == count {
+            return unchanged(numerator, denominator);
+        }
+
+        let lead = |p: &Polynomial<F>| *p.get_terms().last().unwrap();
+        Self {
+            numerator: real_polynomial(&zeros) * lead(&numerator),
+            denominator: real_polynomial(&poles) * lead(&denominator),
+            sample_time,
+        }
+    }
+
+    #[must_use]
+    pub const fn get_numerator(&self) -> &Polynomial<F> {
+        &self.numerator
+    }
+
+    #[must_use]
+    pub const fn get_denominator(&self) -> &Polynomial<F> {
+        &self.denominator
+    }
+
+    #[must_use]
+    pub const fn get_sample_time(&self) -> Option<F> {
+        self.sample_time
+    }
+
+    #[must_use]
+    pub const fn is_discrete(&self) -> bool {
+        self.sample_time.is_some()
+    }
+
+    /// Evaluates `N(s) / D(s)`, infinite at the poles
+    #[must_use]
+    pub fn eval(&self, s: Complex<F>) -> Complex<F> {
+        self.numerator.eval_complex(s) / self.denominator.eval_complex(s)
+    }
+
+    #[must_use]
+    pub fn poles(&self) -> Vec<Complex<F>> {
+        Self::roots_of(&self.denominator)
+    }
+
+    #[must_use]
+    pub fn zeros(&self) -> Vec<Complex<F>> {
+        Self::roots_of(&self.numerator)
+    }
+
+    fn roots_of(poly: &Polynomial<F>) -> Vec<Complex<F>> {
+        if poly.is_zero() {
+            return Vec::new();
+        }
+
+        let mut out = vec![Complex::zero(); poly.order()];
+        poly.find_roots(&mut out, F::epsilon());
+        out
+    }
+
+    /// The gain at `s = 0`, or `z = 1` in discrete time, infinite when there are
+    /// more poles than zeros there
+    #[must_use]
+    pub fn dc_gain(&self) -> F {
+        self.low_frequency_limit(0)
+    }
+
+    /// Computes the limit of `s^n G(s)` as `s` goes to 0, or of `((z - 1) / T)^n G(z)`
+    /// as `z` goes to 1 in discrete time
+    fn low_frequency_limit(&self, n: usize) -> F {
+        if self.numerator.is_zero() {
+            return F::zero();
+        }
+
+        let (point, scale) = self.sample_time.map_or_else(
+            || (F::zero(), F::one()),
+            |t| (F::one(), t.powi(i32::try_from(n).unwrap()).recip()),
+        );
+        let (num, zeros) = self.numerator.deflate(point);
+        let (den, poles) = self.denominator.deflate(point);
+        let ratio = num.eval(point) / den.eval(point) * scale;
+
+        match (n + zeros).cmp(&poles) {
+            Ordering::Equal => ratio,
+            Ordering::Greater => F::zero(),
+            Ordering::Less => F::infinity() * ratio.signum(),
+        }
+    }
+
+    /// The gain as `s` goes to infinity, zero for strictly proper systems and
+    /// infinite for improper ones
+    #[must_use]
+    pub fn high_frequency_gain(&self) -> F {
+        if self.numerator.is_zero() {
+            return F::zero();
+        }
+
+        let n = *self.numerator.get_terms().last().unwrap();
+        let d = *self.denominator.get_terms().last().unwrap();
+
+        match self.relative_degree() {
+            0 => n / d,
+            x if x > 0 => F::zero(),
+            _ => F::infinity() * (n / d).signum(),
+        }
+    }
+
+    /// Order of the denominator minus order of the numerator
+    #[must_use]
+    pub const fn relative_degree(&self) -> isize {
+        self.denominator.order().cast_signed() - self.numerator.order().cast_signed()
+    }
+
+    /// Whether the numerator order does not exceed the denominator order
+    #[must_use]
+    pub const fn is_proper(&self) -> bool {
+        self.relative_degree() >= 0
+    }
+
+    /// Whether the numerator order is lower than the denominator order
+    #[must_use]
+    pub const fn is_strictly_proper(&self) -> bool {
+        self.relative_degree() > 0
+    }
+}
+
+/// Closes the loop of `g` with `h` in the feedback path
+///
+/// # Errors
+///
+/// `Error::SampleTimeMismatch` if `g` and `h` have different sample times, and
+/// `Error::EmptyPolynomial` if the closed loop is degenerate, i.e. `1 ± G H = 0`
+pub fn feedback<F: Float + Debug>(
+    g: &TransferFunction<F>,
+    h: &TransferFunction<F>,
+    sign: FeedbackSign,
+) -> Result<TransferFunction<F>> {
+    if g.sample_time != h.sample_time {
+        return Err(Error::SampleTimeMismatch);
+    }
+
+    let sign = match sign {
+        FeedbackSign::Negative => F::one(),
+        FeedbackSign::Positive => -F::one(),
+    };
+
+    let numerator = &g.numerator * &h.denominator;
+    let denominator = Polynomial::from_sum(
+        F::one(),
+        &(&g.denominator * &h.denominator),
+        sign,
+        &(&g.numerator * &h.numerator),
+    );
+
+    if denominator.is_zero() {
+        return Err(Error::EmptyPolynomial);
+    }
+
+    Ok(TransferFunction::reduced(
+        numerator,
+        denominator,
+        g.sample_time,
+    ))
+}
+
+impl<F: Float + Debug> TransferFunction<F> {
+    fn parallel(&self, rhs: &Self, sign: F) -> Self {
+        assert_eq!(
+            self.sample_time, rhs.sample_time,
+            "the sample times must match"
+        );
+
+        if self.denominator == rhs.denominator {
+            return Self::reduced(
+                Polynomial::from_sum(F::one(), &self.numerator, sign, &rhs.numerator),
+                self.denominator.clone(),
+                self.sample_time,
+            );
+        }
+
+        Self::reduced(
+            Polynomial::from_sum(
+                F::one(),
+                &(&self.numerator * &rhs.denominator),
+                sign,
+                &(&rhs.numerator * &self.denominator),
+            ),
+            &self.denominator * &rhs.denominator,
+            self.sample_time,
+        )
+    }
+}
+
+/// Series connection
+///
+/// # Panics
+///
+/// If the sample times differ
+impl<F: Float + Debug> Mul for &TransferFunction<F> {
+    type Output = TransferFunction<F>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(
+            self.sample_time, rhs.sample_time,
+            "the sample times must match"
+        );
+
+        TransferFunction::reduced(
+            &self.numerator * &rhs.numerator,
+            &self.denominator * &rhs.denominator,
+            self.sample_time,
+        )
+    }
+}
+
+impl<F: Float + Debug> Mul for TransferFunction<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+/// Parallel connection
+///
+/// # Panics
+///
+/// If the sample times differ
+impl<F: Float + Debug> Add for &TransferFunction<F> {
+    type Output = TransferFunction<F>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.parallel(rhs, F::one())
+    }
+}
+
+impl<F: Float + Debug> Add for TransferFunction<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+/// Parallel connection with the second branch negated
+///
+/// # Panics
+///
+/// If the sample times differ
+impl<F: Float + Debug> Sub for &TransferFunction<F> {
+    type Output = TransferFunction<F>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.parallel(rhs, -F::one())
+    }
+}
+
+impl<F: Float + Debug> Sub for TransferFunction<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn plant() -> TransferFunction<f64> {
+        // 2 (s + 3) / ((s + 1) (s² + 2s + 5))
+        TransferFunction::from_zpk(
+            &[PolynomialRoot::RealSingle(-3.0)],
+            &[
+                PolynomialRoot::RealSingle(-1.0),
+                PolynomialRoot::ComplexPair(Complex::new(-1.0, 2.0)),
+            ],
+            2.0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn zpk() {
+        let g = plant();
+
+        assert_eq!(g.get_numerator(), &Polynomial::new(vec![6.0, 2.0]));
+        assert_eq!(
+            g.get_denominator(),
+            &Polynomial::new(vec![5.0, 7.0, 3.0, 1.0])
+        );
+
+        let zeros = g.zeros();
+        assert_eq!(zeros.len(), 1);
+        assert_abs_diff_eq!(zeros[0].re, -3.0, epsilon = 1e-12);
+
+        let poles = g.poles();
+        assert_eq!(poles.len(), 3);
+        for p in [
+            Complex::new(-1.0, 0.0),
+            Complex::new(-1.0, 2.0),
+            Complex::new(-1.0, -2.0),
+        ] {
+            assert!(poles.iter().any(|x| (x - p).norm() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn gains_and_degree() {
+        let g = plant();
+
+        assert_abs_diff_eq!(g.dc_gain(), 6.0 / 5.0);
+        assert_abs_diff_eq!(g.high_frequency_gain(), 0.0);
+        assert_eq!(g.relative_degree(), 2);
+        assert!(g.is_proper() && g.is_strictly_proper());
+
+        let s = Complex::new(0.0, 1.0);
+        let expected = (s + 3.0) * 2.0 / ((s + 1.0) * (s * s + s * 2.0 + 5.0));
+        assert_abs_diff_eq!((g.eval(s) - expected).norm(), 0.0, epsilon = 1e-12);
+
+        // (s + 1) / s is biproper, with an infinite DC gain
+        let pi = TransferFunction::from_coefficients(vec![1.0, 1.0], vec![0.0, 1.0]).unwrap();
+        assert!(pi.dc_gain().is_infinite());
+        assert_abs_diff_eq!(pi.high_frequency_gain(), 1.0);
+        assert!(pi.is_proper() && !pi.is_strictly_proper());
+    }
+
+    #[test]
+    fn rejects_zero_denominator() {
+        assert_eq!(
+            TransferFunction::from_coefficients(vec![1.0], vec![0.0]),
+            Err(Error::EmptyPolynomial)
+        );
+        assert_eq!(
+            TransferFunction::from_coefficients(vec![1.0], vec![]),
+            Err(Error::EmptyPolynomial)
+        );
+    }
+
+    #[test]
+    fn block_diagram() {
+        // 1 / s and 1 / (s + 1)
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![0.0, 1.0]).unwrap();
+        let h = TransferFunction::from_coefficients(vec![1.0], vec![1.0, 1.0]).unwrap();
+
+        let series = &g * &h;
+        assert_eq!(series.get_numerator(), &Polynomial::new(vec![1.0]));
+        assert_eq!(
+            series.get_denominator(),
+            &Polynomial::new(vec![0.0, 1.0, 1.0])
+        );
+
+        // 1/s + 1/(s + 1) = (2s + 1) / (s² + s)
+        let sum = &g + &h;
+        assert_eq!(sum.get_numerator(), &Polynomial::new(vec![1.0, 2.0]));
+        assert_eq!(sum.get_denominator(), &Polynomial::new(vec![0.0, 1.0, 1.0]));
+
+        // Same denominators are not squared
+        let twice = g.clone() + g.clone();
+        assert_eq!(twice.get_numerator(), &Polynomial::new(vec![2.0]));
+        assert_eq!(twice.get_denominator(), g.get_denominator());
+        assert!((g.clone() - g.clone()).get_numerator().is_zero());
+
+        // (1 / s) / (1 + 1 / (s (s + 1))) = (s + 1) / (s² + s + 1)
+        let closed = feedback(&g, &h, FeedbackSign::Negative).unwrap();
+        assert_eq!(closed.get_numerator(), &Polynomial::new(vec![1.0, 1.0]));
+        assert_eq!(
+            closed.get_denominator(),
+            &Polynomial::new(vec![1.0, 1.0, 1.0])
+        );
+
+        let one = TransferFunction::from_coefficients(vec![1.0], vec![1.0]).unwrap();
+        assert_eq!(
+            feedback(&one, &one, FeedbackSign::Positive),
+            Err(Error::EmptyPolynomial)
+        );
+
+        let sampled = one.clone().with_sample_time(Some(0.1));
+        assert_eq!(
+            feedback(&one, &sampled, FeedbackSign::Negative),
+            Err(Error::SampleTimeMismatch)
+        );
+    }
+
+    #[test]
+    fn cancels_common_roots() {
+        let coefficients = |p: &Polynomial<f64>, expected: &[f64]| {
+            assert_eq!(p.get_terms().len(), expected.len());
+            for (a, b) in p.get_terms().iter().zip(expected) {
+                assert_abs_diff_eq!(a, b, epsilon = 1e-9);
+            }
+        };
+
+        // (s + 3) / ((s + 1) (s + 2)) × 2 (s + 2) / (s + 4) = 2 (s + 3) / ((s + 1) (s + 4))
+        let plant =
+            TransferFunction::from_coefficients(vec![3.0, 1.0], vec![2.0, 3.0, 1.0]).unwrap();
+        let controller =
+            TransferFunction::from_coefficients(vec![4.0, 2.0], vec![4.0, 1.0]).unwrap();
+        let series = &plant * &controller;
+        coefficients(series.get_numerator(), &[6.0, 2.0]);
+        coefficients(series.get_denominator(), &[4.0, 5.0, 1.0]);
+
+        // A complex pair cancels as a whole, (s² + 2s + 5) / (s + 1) × 1 / (s² + 2s + 5)
+        let notch =
+            TransferFunction::from_coefficients(vec![5.0, 2.0, 1.0], vec![1.0, 1.0]).unwrap();
+        let resonance =
+            TransferFunction::from_coefficients(vec![1.0], vec![5.0, 2.0, 1.0]).unwrap();
+        let series = &notch * &resonance;
+        coefficients(series.get_numerator(), &[1.0]);
+        coefficients(series.get_denominator(), &[1.0, 1.0]);
+
+        // A repeated zero only cancels as many poles as it meets,
+        // (s + 1)² / ((s + 2) (s + 3)) × 1 / (s + 1) = (s + 1) / ((s + 2) (s + 3))
+        let double =
+            TransferFunction::from_coefficients(vec![1.0, 2.0, 1.0], vec![6.0, 5.0, 1.0]).unwrap();
+        let series =
+            &double * &TransferFunction::from_coefficients(vec![1.0], vec![1.0, 1.0]).unwrap();
+        coefficients(series.get_numerator(), &[1.0, 1.0]);
+        coefficients(series.get_denominator(), &[6.0, 5.0, 1.0]);
+
+        // 1 / (s + 1) - 1 / (s + 2) = 1 / ((s + 1) (s + 2)), nothing to cancel
+        let diff = TransferFunction::from_coefficients(vec![1.0], vec![1.0, 1.0]).unwrap()
+            - TransferFunction::from_coefficients(vec![1.0], vec![2.0, 1.0]).unwrap();
+        coefficients(diff.get_numerator(), &[1.0]);
+        coefficients(diff.get_denominator(), &[2.0, 3.0, 1.0]);
+
+        // (s + 1) / (s + 1)² in a loop with unity feedback is 1 / (s + 2)
+        let g = TransferFunction::from_coefficients(vec![1.0, 1.0], vec![1.0, 2.0, 1.0]).unwrap();
+        let one = TransferFunction::from_coefficients(vec![1.0], vec![1.0]).unwrap();
+        let closed = feedback(&g, &one, FeedbackSign::Negative).unwrap();
+        coefficients(closed.get_numerator(), &[1.0]);
+        coefficients(closed.get_denominator(), &[2.0, 1.0]);
+    }
 }
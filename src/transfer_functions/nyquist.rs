@@ -0,0 +1,323 @@
+use std::fmt::Debug;
+
+use num::{Complex, Float};
+
+use super::{frequency::logspace, TransferFunction};
+use crate::{
+    contour::winding_number,
+    polynomials::{roots::RootFinding, Polynomial},
+};
+
+/// Points of each small semicircle around a pole on the imaginary axis
+const INDENTATION_POINTS: usize = 16;
+/// Points of the large semicircle closing the contour
+const ARC_POINTS: usize = 64;
+/// Frequencies per decade of the contour used for counting encirclements
+const VERDICT_POINTS_PER_DECADE: usize = 4;
+
+/// Maps a point of the w-plane back to the z-plane, `z = (1 + w) / (1 - w)`
+fn from_w_plane<F: Float>(w: Complex<F>) -> Complex<F> {
+    (Complex::from(F::one()) + w) / (Complex::from(F::one()) - w)
+}
+
+/// A point of the Nyquist contour and its image by the transfer function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NyquistPoint<F> {
+    pub s: Complex<F>,
+    pub value: Complex<F>,
+}
+
+/// Outcome of the Nyquist criterion for the loop closed around `k G`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NyquistVerdict<F> {
+    pub gain: F,
+    /// Open-loop poles in the open right half plane, or outside the unit circle
+    /// in discrete time, `P`
+    pub open_loop_unstable_poles: usize,
+    /// Counter-clockwise encirclements of `-1/k` by the Nyquist plot
+    pub encirclements: i64,
+    /// Closed-loop poles in the same region, `Z = P - encirclements`
+    pub closed_loop_unstable_poles: i64,
+}
+
+impl<F> NyquistVerdict<F> {
+    #[must_use]
+    pub const fn is_stable(&self) -> bool {
+        self.closed_loop_unstable_poles == 0
+    }
+}
+
+impl<F: Float + Debug> TransferFunction<F> {
+    /// Imaginary parts of the open-loop poles on the imaginary axis, sorted and deduplicated
+    fn imaginary_axis_poles(&self) -> Vec<F> {
+        let tol = F::epsilon().sqrt();
+
+        let mut out = self
+            .poles()
+            .into_iter()
+            .filter(|p| p.re.abs() <= tol * (F::one() + p.norm()))
+            .map(|p| p.im)
+            .collect::<Vec<_>>();
+
+        out.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        out.dedup_by(|a, b| (*a - *b).abs() <= tol * (F::one() + b.abs()));
+        out
+    }
+
+    /// Builds the clockwise Nyquist contour as a closed polygon
+    ///
+    /// It goes up the imaginary axis from `-j radius` to `j radius`, through
+    /// `±jω` for log-spaced `ω` over `TransferFunction::decade_range`. Poles on
+    /// the axis are bypassed to the right by small semicircles, whose radius is
+    /// a thousandth of the distance to the nearest other axis pole (or of 1).
+    /// The contour is then closed by the semicircle of `radius` through the
+    /// right half plane.
+    ///
+    /// In discrete time the contour is built around the right half of the
+    /// w-plane and mapped back by `z = (1 + w) / (1 - w)`. It then runs along
+    /// the unit circle, around its poles from the outside, and is closed by a
+    /// small loop outside `z = -1`, the image of the large semicircle.
+    #[must_use]
+    pub fn nyquist_contour(&self, radius: F, points_per_decade: usize) -> Vec<Complex<F>> {
+        assert!(radius > F::zero(), "the radius must be positive");
+
+        if self.is_discrete() {
+            return self
+                .w_plane()
+                .nyquist_contour(radius, points_per_decade)
+                .into_iter()
+                .map(from_w_plane)
+                .collect();
+        }
+
+        let axis_poles = self.imaginary_axis_poles();
+
+        let gap = axis_poles
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .fold(F::one(), F::min);
+        let indentation = (gap * F::from(1e-3).unwrap()).min(radius * F::from(1e-3).unwrap());
+
+        let (start, end) = self.decade_range();
+        let mut axis = logspace::<F>(start, end, points_per_decade)
+            .into_iter()
+            .filter(|&w| w < radius)
+            .flat_map(|w| [-w, w])
+            .chain([F::zero(), -radius, radius])
+            .filter(|&w| axis_poles.iter().all(|&p| (w - p).abs() > indentation))
+            .map(|w| Complex::new(F::zero(), w))
+            .collect::<Vec<_>>();
+
+        let n = F::from(INDENTATION_POINTS).unwrap();
+        let half_pi = F::from(std::f64::consts::FRAC_PI_2).unwrap();
+        for &p in &axis_poles {
+            axis.extend((0..=INDENTATION_POINTS).map(|i| {
+                let theta =
+                    -half_pi + F::from(std::f64::consts::PI).unwrap() * F::from(i).unwrap() / n;
+                Complex::new(F::zero(), p) + Complex::from_polar(indentation, theta)
+            }));
+        }
+
+        // The indentations bulge to the right, so their imaginary part still increases
+        axis.sort_by(|a, b| a.im.partial_cmp(&b.im).unwrap());
+
+        let n = F::from(ARC_POINTS).unwrap();
+        axis.extend((1..ARC_POINTS).map(|i| {
+            let theta = half_pi - F::from(std::f64::consts::PI).unwrap() * F::from(i).unwrap() / n;
+            Complex::from_polar(radius, theta)
+        }));
+
+        axis
+    }
+
+    /// Evaluates the transfer function along `TransferFunction::nyquist_contour`,
+    /// whose large semicircle is at the end of the decade range
+    ///
+    /// In discrete time it is the decade range of the w-plane, at least a decade
+    /// above 1 so that the semicircle stays away from `w = 1`, the image of `z = ∞`.
+    #[must_use]
+    pub fn nyquist(&self, points_per_decade: usize) -> Vec<NyquistPoint<F>> {
+        let end = if self.is_discrete() {
+            self.w_plane().decade_range().1.max(1)
+        } else {
+            self.decade_range().1
+        };
+        let radius = F::from(10).unwrap().powi(end);
+
+        self.nyquist_contour(radius, points_per_decade)
+            .into_iter()
+            .map(|s| NyquistPoint {
+                s,
+                value: self.eval(s),
+            })
+            .collect()
+    }
+
+    /// Applies the Nyquist criterion to the loop closed around `k G`
+    ///
+    /// Counts the encirclements of `-1/k` as those of the origin by `1 + k G`,
+    /// along a contour large enough to hold every closed-loop pole. Returns
+    /// `None` when the plot goes through `-1/k`, i.e. when a closed-loop pole
+    /// lies on the imaginary axis, and for a zero gain, which puts `-1/k` at infinity.
+    ///
+    /// In discrete time the criterion is applied in the w-plane, where the unit
+    /// circle becomes the imaginary axis. It returns `None` when a closed-loop
+    /// pole lies on the unit circle, including at `z = -1`.
+    #[must_use]
+    pub fn nyquist_stability(&self, gain: F) -> Option<NyquistVerdict<F>> {
+        if gain.is_zero() {
+            return None;
+        }
+
+        if self.is_discrete() {
+            return self.discrete_nyquist_stability(gain);
+        }
+
+        let characteristic =
+            Polynomial::from_sum(F::one(), &self.denominator, gain, &self.numerator);
+        if characteristic.is_zero() {
+            return None;
+        }
+
+        let (_, end) = self.decade_range();
+        let (_, bound) = characteristic.determine_roots_bounds();
+        let radius = F::from(10)
+            .unwrap()
+            .powi(end)
+            .max(bound * F::from(2).unwrap());
+
+        let contour = self.nyquist_contour(radius, VERDICT_POINTS_PER_DECADE);
+        let winding = winding_number(
+            |s| characteristic.eval_complex(s) / self.denominator.eval_complex(s),
+            &contour,
+        )?;
+
+        let tol = F::epsilon().sqrt();
+        let open_loop_unstable_poles = self
+            .poles()
+            .iter()
+            .filter(|p| p.re > tol * (F::one() + p.norm()))
+            .count();
+
+        Some(NyquistVerdict {
+            gain,
+            open_loop_unstable_poles,
+            encirclements: winding,
+            closed_loop_unstable_poles: i64::try_from(open_loop_unstable_poles).unwrap() - winding,
+        })
+    }
+
+    fn discrete_nyquist_stability(&self, gain: F) -> Option<NyquistVerdict<F>> {
+        let tol = F::epsilon().sqrt();
+
+        // The w-plane sends z = -1 to infinity, out of reach of the contour
+        let characteristic =
+            Polynomial::from_sum(F::one(), &self.denominator, gain, &self.numerator);
+        let scale = characteristic
+            .get_terms()
+            .iter()
+            .fold(F::zero(), |acc, x| acc + x.abs());
+        if characteristic.eval(-F::one()).abs() <= tol * scale {
+            return None;
+        }
+
+        self.w_plane().nyquist_stability(gain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::root_locus::RootLocus;
+
+    #[test]
+    fn contour_bypasses_axis_poles() {
+        // 1 / (s (s² + 1))
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![0.0, 1.0, 0.0, 1.0]).unwrap();
+
+        let contour = g.nyquist_contour(100.0, 10);
+        assert!(contour
+            .iter()
+            .all(|s| s.re >= 0.0 && s.norm() <= 100.0 + 1e-9));
+
+        let points = g.nyquist(10);
+        assert!(points
+            .iter()
+            .all(|x| x.value.re.is_finite() && x.value.im.is_finite()));
+    }
+
+    #[test]
+    fn matches_root_locus() {
+        // 1 / (s (s + 1) (s + 2)), unstable above k = 6 with two right half plane poles
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![0.0, 2.0, 3.0, 1.0]).unwrap();
+        let rl = RootLocus::from_open_loop(&g).unwrap();
+
+        for k in [0.5, 3.0, 10.0, 100.0] {
+            let verdict = g.nyquist_stability(k).unwrap();
+            assert_eq!(verdict.open_loop_unstable_poles, 0);
+            assert_eq!(verdict.is_stable(), rl.is_stable_at(k));
+        }
+        assert_eq!(
+            g.nyquist_stability(10.0)
+                .unwrap()
+                .closed_loop_unstable_poles,
+            2
+        );
+
+        // The closed-loop poles ±j√2 are on the imaginary axis at k = 6
+        assert!(g.nyquist_stability(6.0).is_none());
+        assert!(g.nyquist_stability(0.0).is_none());
+    }
+
+    #[test]
+    fn discrete_matches_root_locus() {
+        // 1 / (z (z - 0.5)) closes into z² - 0.5z + k, with poles on the unit circle at k = 1
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![0.0, -0.5, 1.0])
+            .unwrap()
+            .with_sample_time(Some(0.1));
+        let rl = RootLocus::from_open_loop(&g).unwrap();
+
+        for k in [0.5, 0.9, 2.0, 10.0] {
+            let verdict = g.nyquist_stability(k).unwrap();
+            assert_eq!(verdict.open_loop_unstable_poles, 0);
+            assert_eq!(verdict.is_stable(), rl.is_stable_at(k));
+        }
+        assert_eq!(
+            g.nyquist_stability(2.0).unwrap().closed_loop_unstable_poles,
+            2
+        );
+        assert!(g.nyquist_stability(1.0).is_none());
+
+        // The contour goes around the outside of the unit circle
+        let points = g.nyquist(10);
+        assert!(points.iter().all(|x| x.s.norm() >= 1.0 - 1e-9));
+        assert!(points
+            .iter()
+            .all(|x| x.value.re.is_finite() && x.value.im.is_finite()));
+
+        // 1 / (z - 2) closes into z - 2 + k, stable for 1 < k < 3
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![-2.0, 1.0])
+            .unwrap()
+            .with_sample_time(Some(0.1));
+
+        let verdict = g.nyquist_stability(2.0).unwrap();
+        assert_eq!(verdict.open_loop_unstable_poles, 1);
+        assert!(verdict.is_stable());
+        assert!(!g.nyquist_stability(0.5).unwrap().is_stable());
+        assert!(g.nyquist_stability(3.0).is_none());
+    }
+
+    #[test]
+    fn unstable_open_loop() {
+        // (s + 1) / (s (s - 1)) closes into s² + (k - 1) s + k, stable for k > 1
+        let g = TransferFunction::from_coefficients(vec![1.0, 1.0], vec![0.0, -1.0, 1.0]).unwrap();
+
+        let low = g.nyquist_stability(0.5).unwrap();
+        assert_eq!(low.open_loop_unstable_poles, 1);
+        assert_eq!(low.closed_loop_unstable_poles, 2);
+
+        let high = g.nyquist_stability(2.0).unwrap();
+        assert_eq!(high.encirclements, 1);
+        assert!(high.is_stable());
+    }
+}
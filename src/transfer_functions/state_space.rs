@@ -0,0 +1,226 @@
+use std::fmt::Debug;
+
+use num::{Complex, Float};
+
+use super::TransferFunction;
+use crate::{
+    error::{Error, Result},
+    matrix::Matrix,
+    polynomials::Polynomial,
+};
+
+/// A single-input single-output state-space model
+///
+/// `x' = A x + B u` and `y = C x + D u`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateSpace<F> {
+    a: Matrix<F>,
+    b: Matrix<F>,
+    c: Matrix<F>,
+    d: F,
+}
+
+impl<F: Float + Debug> StateSpace<F> {
+    /// # Errors
+    ///
+    /// `Error::DimensionMismatch` unless `A` is `n × n`, `B` is `n × 1` and
+    /// `C` is `1 × n`, and `Error::NonFiniteCoefficients` if any element is NaN or infinite
+    pub fn new(a: Matrix<F>, b: Matrix<F>, c: Matrix<F>, d: F) -> Result<Self> {
+        let order = a.rows();
+        if !a.is_square()
+            || (b.rows(), b.cols()) != (order, 1)
+            || (c.rows(), c.cols()) != (1, order)
+        {
+            return Err(Error::DimensionMismatch);
+        }
+        if !a.is_finite() || !b.is_finite() || !c.is_finite() || !d.is_finite() {
+            return Err(Error::NonFiniteCoefficients);
+        }
+
+        Ok(Self { a, b, c, d })
+    }
+
+    /// Splits a proper transfer function into `D` and the strictly proper rest
+    ///
+    /// Returns the denominator coefficients divided by the leading one, without
+    /// it, then the numerator coefficients of the rest and `D`
+    fn normalized(tf: &TransferFunction<F>) -> Result<(Vec<F>, Vec<F>, F)> {
+        let (num, den) = (tf.get_numerator(), tf.get_denominator());
+        if !tf.is_proper() {
+            return Err(Error::ImproperSystem {
+                numerator_order: num.order(),
+                denominator_order: den.order(),
+            });
+        }
+
+        let n = den.order();
+        let lead = den.get_terms()[n];
+        let a = den.get_terms()[..n]
+            .iter()
+            .map(|&x| x / lead)
+            .collect::<Vec<_>>();
+
+        let coefficient = |i| num.get_terms().get(i).copied().unwrap_or_else(F::zero) / lead;
+        let d = coefficient(n);
+        let b = (0..n).map(|i| coefficient(i) - d * a[i]).collect();
+
+        Ok((a, b, d))
+    }
+
+    /// Builds the controllable canonical form, the one `TransferFunction::response` simulates
+    ///
+    /// # Errors
+    ///
+    /// `Error::ImproperSystem` if the transfer function is not proper
+    pub fn controllable_canonical(tf: &TransferFunction<F>) -> Result<Self> {
+        let (a, b, d) = Self::normalized(tf)?;
+        let n = a.len();
+
+        let mut state = Matrix::zeros(n, n);
+        for i in 1..n {
+            state[(i - 1, i)] = F::one();
+        }
+        for (j, &x) in a.iter().enumerate() {
+            state[(n - 1, j)] = -x;
+        }
+
+        let mut input = Matrix::zeros(n, 1);
+        if n > 0 {
+            input[(n - 1, 0)] = F::one();
+        }
+
+        Self::new(state, input, Matrix::new(1, n, b), d)
+    }
+
+    /// Builds the observable canonical form, the dual of the controllable one
+    ///
+    /// # Errors
+    ///
+    /// `Error::ImproperSystem` if the transfer function is not proper
+    pub fn observable_canonical(tf: &TransferFunction<F>) -> Result<Self> {
+        let controllable = Self::controllable_canonical(tf)?;
+
+        Ok(Self {
+            a: controllable.a.transpose(),
+            b: controllable.c.transpose(),
+            c: controllable.b.transpose(),
+            d: controllable.d,
+        })
+    }
+
+    #[must_use]
+    pub const fn get_a(&self) -> &Matrix<F> {
+        &self.a
+    }
+
+    #[must_use]
+    pub const fn get_b(&self) -> &Matrix<F> {
+        &self.b
+    }
+
+    #[must_use]
+    pub const fn get_c(&self) -> &Matrix<F> {
+        &self.c
+    }
+
+    #[must_use]
+    pub const fn get_d(&self) -> F {
+        self.d
+    }
+
+    #[must_use]
+    pub const fn order(&self) -> usize {
+        self.a.rows()
+    }
+
+    /// The poles of the model, eigenvalues of `A`
+    #[must_use]
+    pub fn eigenvalues(&self) -> Vec<Complex<F>> {
+        self.a.eigenvalues()
+    }
+
+    /// Computes `C adj(sI - A) B / det(sI - A) + D`, without cancelling the
+    /// uncontrollable or unobservable modes
+    ///
+    /// # Errors
+    ///
+    /// Same as `TransferFunction::new`
+    pub fn to_transfer_function(&self) -> Result<TransferFunction<F>> {
+        let (det, adjugate) = self.a.resolvent();
+        let n = self.order();
+
+        let mut numerator = vec![F::zero(); n + 1];
+        for (k, m) in adjugate.iter().enumerate() {
+            numerator[n - 1 - k] = (&(&self.c * m) * &self.b)[(0, 0)];
+        }
+
+        let numerator = Polynomial::new(numerator) + det.clone() * self.d;
+        TransferFunction::new(numerator, det)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn round_trip() {
+        // (s² + 3s + 5) / (2s² + 4s + 8), with D = 1/2
+        let g =
+            TransferFunction::from_coefficients(vec![5.0, 3.0, 1.0], vec![8.0, 4.0, 2.0]).unwrap();
+
+        for ss in [
+            StateSpace::controllable_canonical(&g).unwrap(),
+            StateSpace::observable_canonical(&g).unwrap(),
+        ] {
+            assert_eq!(ss.order(), 2);
+            assert_abs_diff_eq!(ss.get_d(), 0.5);
+
+            let back = ss.to_transfer_function().unwrap();
+            assert_eq!(back.get_numerator(), &Polynomial::new(vec![2.5, 1.5, 0.5]));
+            assert_eq!(
+                back.get_denominator(),
+                &Polynomial::new(vec![4.0, 2.0, 1.0])
+            );
+
+            let s = Complex::new(0.3, 1.7);
+            assert_abs_diff_eq!((back.eval(s) - g.eval(s)).norm(), 0.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn mass_spring_damper() {
+        // m x'' + c x' + k x = u with m = 1, c = 2, k = 5, measuring the position
+        let a = Matrix::from_rows(&[&[0.0, 1.0], &[-5.0, -2.0]]);
+        let b = Matrix::from_rows(&[&[0.0], &[1.0]]);
+        let c = Matrix::from_rows(&[&[1.0, 0.0]]);
+        let ss = StateSpace::new(a, b, c, 0.0).unwrap();
+
+        let g = ss.to_transfer_function().unwrap();
+        assert_eq!(g.get_numerator(), &Polynomial::new(vec![1.0]));
+        assert_eq!(g.get_denominator(), &Polynomial::new(vec![5.0, 2.0, 1.0]));
+
+        for p in ss.eigenvalues() {
+            assert_abs_diff_eq!(p.re, -1.0, epsilon = 1e-9);
+            assert_abs_diff_eq!(p.im.abs(), 2.0, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_models() {
+        assert_eq!(
+            StateSpace::new(
+                Matrix::identity(2),
+                Matrix::zeros(3, 1),
+                Matrix::zeros(1, 2),
+                0.0
+            ),
+            Err(Error::DimensionMismatch)
+        );
+
+        let improper =
+            TransferFunction::from_coefficients(vec![0.0, 0.0, 1.0], vec![1.0, 1.0]).unwrap();
+        assert!(StateSpace::controllable_canonical(&improper).is_err());
+    }
+}
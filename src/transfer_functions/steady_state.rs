@@ -0,0 +1,196 @@
+use std::fmt::{Debug, Display};
+
+use num::Float;
+use rand::{distributions::Standard, prelude::Distribution};
+
+use super::{time_response::InputSignal, TransferFunction};
+use crate::polynomials::root_locus::RootLocus;
+
+/// Static error constants of an open loop, which give the steady-state errors
+/// of the loop closed with unity negative feedback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorConstants<F> {
+    /// `Kp = lim G(s)` as `s` goes to 0
+    pub position: F,
+    /// `Kv = lim s G(s)`
+    pub velocity: F,
+    /// `Ka = lim s² G(s)`
+    pub acceleration: F,
+}
+
+impl<F: Float> ErrorConstants<F> {
+    /// The constants of `k G`
+    #[must_use]
+    pub fn scaled(&self, gain: F) -> Self {
+        // Keeps 0 × ∞ from turning into NaN
+        let scale = |x: F| if gain.is_zero() { F::zero() } else { x * gain };
+
+        Self {
+            position: scale(self.position),
+            velocity: scale(self.velocity),
+            acceleration: scale(self.acceleration),
+        }
+    }
+
+    /// The steady-state error to the input, only meaningful when the closed loop is stable
+    ///
+    /// `1 / (1 + Kp)` for a step, `1 / Kv` for a ramp and `1 / Ka` for a parabola.
+    /// The impulse response of a stable loop always dies out.
+    #[must_use]
+    pub fn steady_state_error(&self, input: InputSignal) -> F {
+        match input {
+            InputSignal::Impulse => F::zero(),
+            InputSignal::Step => (F::one() + self.position).recip(),
+            InputSignal::Ramp => self.velocity.recip(),
+            InputSignal::Parabola => self.acceleration.recip(),
+        }
+    }
+}
+
+impl<F: Float + Debug> TransferFunction<F> {
+    /// Number of integrators, poles at `s = 0` (or `z = 1`) not cancelled by zeros
+    #[must_use]
+    pub fn system_type(&self) -> usize {
+        if self.numerator.is_zero() {
+            return 0;
+        }
+
+        let point = if self.is_discrete() {
+            F::one()
+        } else {
+            F::zero()
+        };
+        let (_, zeros) = self.numerator.deflate(point);
+        let (_, poles) = self.denominator.deflate(point);
+
+        poles.saturating_sub(zeros)
+    }
+
+    /// Computes `Kp`, `Kv` and `Ka`, with `(z - 1) / T` in place of `s` in discrete time
+    #[must_use]
+    pub fn error_constants(&self) -> ErrorConstants<F> {
+        ErrorConstants {
+            position: self.low_frequency_limit(0),
+            velocity: self.low_frequency_limit(1),
+            acceleration: self.low_frequency_limit(2),
+        }
+    }
+}
+
+impl<F: Float + Display + Debug> RootLocus<F>
+where
+    Standard: Distribution<F>,
+{
+    /// The error constants of the open loop at `gain`
+    #[must_use]
+    pub fn error_constants(&self, gain: F) -> ErrorConstants<F> {
+        self.open_loop().error_constants().scaled(gain)
+    }
+
+    /// The steady-state error to the input at `gain`, only meaningful within the
+    /// stable gains
+    #[must_use]
+    pub fn steady_state_error(&self, gain: F, input: InputSignal) -> F {
+        self.error_constants(gain).steady_state_error(input)
+    }
+
+    /// The lowest gain whose steady-state error to the input is at most `max_error`
+    ///
+    /// The error shrinks as the gain grows when the error constant is positive.
+    /// Returns `None` when no gain is enough, i.e. when the constant is zero or
+    /// negative. Whether the loop is stable at that gain is left to check against
+    /// the stable gains.
+    #[must_use]
+    pub fn min_gain_for_error(&self, input: InputSignal, max_error: F) -> Option<F> {
+        assert!(max_error > F::zero(), "the error bound must be positive");
+
+        let constants = self.open_loop().error_constants();
+        let constant = match input {
+            InputSignal::Impulse => return Some(F::zero()),
+            InputSignal::Step => constants.position,
+            InputSignal::Ramp => constants.velocity,
+            InputSignal::Parabola => constants.acceleration,
+        };
+
+        // 1 / (1 + k Kp) <= e for a step, 1 / (k K) <= e otherwise
+        let needed = if input == InputSignal::Step {
+            max_error.recip() - F::one()
+        } else {
+            max_error.recip()
+        };
+
+        if needed <= F::zero() || constant.is_infinite() {
+            Some(F::zero())
+        } else if constant > F::zero() {
+            Some(needed / constant)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::{
+        root_locus::{Domain, RootLocus},
+        Polynomial,
+    };
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn type_one() {
+        // 2 / (s (s + 4)), so Kv = 0.5
+        let g = TransferFunction::from_coefficients(vec![2.0], vec![0.0, 4.0, 1.0]).unwrap();
+
+        assert_eq!(g.system_type(), 1);
+
+        let constants = g.error_constants();
+        assert!(constants.position.is_infinite());
+        assert_abs_diff_eq!(constants.velocity, 0.5);
+        assert_abs_diff_eq!(constants.acceleration, 0.0);
+
+        assert_abs_diff_eq!(constants.steady_state_error(InputSignal::Step), 0.0);
+        assert_abs_diff_eq!(constants.steady_state_error(InputSignal::Ramp), 2.0);
+        assert!(constants
+            .steady_state_error(InputSignal::Parabola)
+            .is_infinite());
+    }
+
+    #[test]
+    fn against_gain() {
+        // 1 / ((s + 1) (s + 2)), with Kp = k / 2
+        let a = Polynomial::new(vec![2.0, 3.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+        let rl = RootLocus::new(a, b).unwrap();
+
+        assert_abs_diff_eq!(rl.steady_state_error(8.0, InputSignal::Step), 0.2);
+        assert_abs_diff_eq!(rl.error_constants(0.0).position, 0.0);
+
+        // 1 / (1 + k / 2) <= 0.1 needs k >= 18
+        let k = rl.min_gain_for_error(InputSignal::Step, 0.1).unwrap();
+        assert_abs_diff_eq!(k, 18.0, epsilon = 1e-12);
+        assert_eq!(rl.min_gain_for_error(InputSignal::Ramp, 0.1), None);
+        assert_eq!(rl.min_gain_for_error(InputSignal::Step, 2.0), Some(0.0));
+    }
+
+    #[test]
+    fn discrete() {
+        // T / (z - 1) is a discrete integrator, with Kv = 1
+        let t = 0.1;
+        let g = TransferFunction::from_coefficients(vec![t], vec![-1.0, 1.0])
+            .unwrap()
+            .with_sample_time(Some(t));
+
+        assert_eq!(g.system_type(), 1);
+        assert_abs_diff_eq!(g.error_constants().velocity, 1.0, epsilon = 1e-12);
+
+        let rl = RootLocus::from_open_loop(&g).unwrap();
+        assert_eq!(rl.get_domain(), Domain::Discrete { sample_time: t });
+        assert_abs_diff_eq!(
+            rl.steady_state_error(4.0, InputSignal::Ramp),
+            0.25,
+            epsilon = 1e-12
+        );
+    }
+}
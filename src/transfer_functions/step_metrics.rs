@@ -0,0 +1,248 @@
+use std::fmt::{Debug, Display};
+
+use num::Float;
+use rand::{distributions::Standard, prelude::Distribution};
+
+use super::{
+    time_response::{InputSignal, TimePoint},
+    TransferFunction,
+};
+use crate::{error::Result, polynomials::root_locus::RootLocus};
+
+/// Fewest and most steps of the simulation behind `TransferFunction::step_metrics`
+const MIN_STEPS: usize = 2000;
+const MAX_STEPS: usize = 200_000;
+
+/// Performance of a step response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepMetrics<F> {
+    /// Value the response settles to
+    pub steady_state: F,
+    /// Time from 10% to 90% of the steady-state value, `None` if never reached
+    pub rise_time: Option<F>,
+    /// Time of the largest value, relative to the steady-state value
+    pub peak_time: F,
+    /// Largest value
+    pub peak: F,
+    /// Percentage by which the peak exceeds the steady-state value
+    pub overshoot: F,
+    /// Percentage of the steady-state value reached in the opposite direction
+    pub undershoot: F,
+    /// Time after which the response stays within the band around the
+    /// steady-state value, `None` if it has not settled by the end
+    pub settling_time: Option<F>,
+}
+
+/// First time the normalized response reaches `level`, interpolated between samples
+fn crossing_time<F: Float>(response: &[TimePoint<F>], steady_state: F, level: F) -> Option<F> {
+    response.windows(2).find_map(|w| {
+        let (a, b) = (w[0].value / steady_state, w[1].value / steady_state);
+        (a < level && b >= level)
+            .then(|| w[0].time + (w[1].time - w[0].time) * (level - a) / (b - a))
+    })
+}
+
+impl<F: Float> StepMetrics<F> {
+    /// Measures a simulated step response against its steady-state value
+    ///
+    /// `settling_band` is relative to the steady-state value, `0.02` for the usual 2% band.
+    /// The ratios are meaningless for a zero steady-state value, they are then NaN.
+    #[must_use]
+    pub fn from_response(response: &[TimePoint<F>], steady_state: F, settling_band: F) -> Self {
+        assert!(!response.is_empty(), "the response must have samples");
+        assert!(
+            settling_band > F::zero(),
+            "the settling band must be positive"
+        );
+
+        let hundred = F::from(100).unwrap();
+
+        let normalized = |x: &TimePoint<F>| x.value / steady_state;
+        let peak = response.iter().fold(response[0], |a, &b| {
+            if normalized(&b) > normalized(&a) {
+                b
+            } else {
+                a
+            }
+        });
+        let lowest = response.iter().map(normalized).fold(F::infinity(), F::min);
+
+        let rise_time = crossing_time(response, steady_state, F::from(0.1).unwrap())
+            .zip(crossing_time(response, steady_state, F::from(0.9).unwrap()))
+            .map(|(low, high)| high - low);
+
+        let band = settling_band * steady_state.abs();
+        let settling_time = response
+            .iter()
+            .rposition(|x| (x.value - steady_state).abs() > band)
+            .map_or(Some(response[0].time), |i| {
+                response.get(i + 1).map(|x| x.time)
+            });
+
+        Self {
+            steady_state,
+            rise_time,
+            peak_time: peak.time,
+            peak: peak.value,
+            overshoot: ((normalized(&peak) - F::one()) * hundred).max(F::zero()),
+            undershoot: (-lowest * hundred).max(F::zero()),
+            settling_time,
+        }
+    }
+}
+
+impl<F: Float + Debug> TransferFunction<F> {
+    /// Time long enough for the slowest stable pole to die out, `10 / σ` with
+    /// `σ` its decay rate, or 10 s without any stable pole
+    ///
+    /// In discrete time the poles are mapped to the s-plane with `s = ln(z) / T`,
+    /// and the horizon is at least 10 sample times.
+    #[must_use]
+    pub fn response_horizon(&self) -> F {
+        let slowest = self
+            .poles()
+            .into_iter()
+            .map(|p| self.sample_time.map_or(p, |t| p.ln() / t))
+            .map(|p| -p.re)
+            .filter(|&x| x > F::zero())
+            .reduce(F::min);
+
+        let ten = F::from(10).unwrap();
+        let horizon = slowest.map_or(ten, |x| ten / x);
+        self.sample_time.map_or(horizon, |t| horizon.max(ten * t))
+    }
+
+    /// Simulates the step response over `TransferFunction::response_horizon` and
+    /// measures it
+    ///
+    /// The steady-state value is the DC gain, or the last sample when it is
+    /// infinite. The step is short enough to follow the fastest pole, in
+    /// continuous time.
+    ///
+    /// # Errors
+    ///
+    /// Same as `TransferFunction::response`
+    pub fn step_metrics(&self, settling_band: F) -> Result<StepMetrics<F>> {
+        let horizon = self.response_horizon();
+
+        let fastest = self
+            .poles()
+            .iter()
+            .map(|p| p.norm())
+            .filter(|x| x.is_finite())
+            .fold(F::zero(), F::max);
+        let steps = (horizon * fastest * F::from(20).unwrap())
+            .to_usize()
+            .unwrap_or(MAX_STEPS)
+            .clamp(MIN_STEPS, MAX_STEPS);
+
+        let response = self.response(InputSignal::Step, horizon, steps)?;
+
+        let dc = self.dc_gain();
+        let steady_state = if dc.is_finite() {
+            dc
+        } else {
+            response.last().unwrap().value
+        };
+
+        Ok(StepMetrics::from_response(
+            &response,
+            steady_state,
+            settling_band,
+        ))
+    }
+}
+
+impl<F: Float + Display + Debug> RootLocus<F>
+where
+    Standard: Distribution<F>,
+{
+    /// Measures the closed-loop step response at each gain
+    ///
+    /// # Errors
+    ///
+    /// Same as `RootLocus::closed_loop` and `TransferFunction::step_metrics`
+    pub fn step_metrics(&self, gains: &[F], settling_band: F) -> Result<Vec<StepMetrics<F>>> {
+        gains
+            .iter()
+            .map(|&k| self.closed_loop(k)?.step_metrics(settling_band))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{polynomials::Polynomial, transfer_functions::discretization::Discretization};
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn second_order() {
+        // 1 / (s² + s + 1), ζ = 0.5 and ωn = 1
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![1.0, 1.0, 1.0]).unwrap();
+        let metrics = g.step_metrics(0.02).unwrap();
+
+        let zeta: f64 = 0.5;
+        let wd = zeta.mul_add(-zeta, 1.0).sqrt();
+
+        assert_abs_diff_eq!(metrics.steady_state, 1.0);
+        assert_abs_diff_eq!(
+            metrics.overshoot,
+            100.0 * (-std::f64::consts::PI * zeta / wd).exp(),
+            epsilon = 1e-3
+        );
+        assert_abs_diff_eq!(metrics.peak_time, std::f64::consts::PI / wd, epsilon = 1e-2);
+        assert_abs_diff_eq!(metrics.undershoot, 0.0);
+
+        let rise = metrics.rise_time.unwrap();
+        assert!(rise > 1.5 && rise < 1.8);
+
+        // About 4 / (ζ ωn)
+        let settling = metrics.settling_time.unwrap();
+        assert!(settling > 7.0 && settling < 9.0);
+    }
+
+    #[test]
+    fn discrete_second_order() {
+        // The zero-order hold equivalent of 1 / (s² + s + 1) is sampled from the same response
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![1.0, 1.0, 1.0]).unwrap();
+        let continuous = g.step_metrics(0.02).unwrap();
+        let discrete = g
+            .c2d(0.01, Discretization::ZeroOrderHold)
+            .unwrap()
+            .step_metrics(0.02)
+            .unwrap();
+
+        assert_abs_diff_eq!(discrete.steady_state, 1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(discrete.overshoot, continuous.overshoot, epsilon = 1e-2);
+        assert_abs_diff_eq!(discrete.peak_time, continuous.peak_time, epsilon = 1e-2);
+        assert_abs_diff_eq!(
+            discrete.settling_time.unwrap(),
+            continuous.settling_time.unwrap(),
+            epsilon = 1e-2
+        );
+    }
+
+    #[test]
+    fn non_minimum_phase() {
+        // (1 - s) / (s + 1)² first moves the wrong way
+        let g = TransferFunction::from_coefficients(vec![1.0, -1.0], vec![1.0, 2.0, 1.0]).unwrap();
+        let metrics = g.step_metrics(0.02).unwrap();
+
+        assert!(metrics.undershoot > 10.0);
+        assert_abs_diff_eq!(metrics.overshoot, 0.0);
+    }
+
+    #[test]
+    fn per_gain() {
+        // 1 / (s (s + 1) (s + 2)) overshoots more as the gain grows
+        let a = Polynomial::new(vec![0.0, 2.0, 3.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+        let rl = RootLocus::new(a, b).unwrap();
+
+        let metrics = rl.step_metrics(&[0.5, 2.0, 4.0], 0.02).unwrap();
+        assert_eq!(metrics.len(), 3);
+        assert!(metrics.iter().all(|x| (x.steady_state - 1.0).abs() < 1e-12));
+        assert!(metrics.windows(2).all(|w| w[0].overshoot < w[1].overshoot));
+    }
+}
@@ -0,0 +1,354 @@
+use std::fmt::Debug;
+
+use num::Float;
+
+use super::TransferFunction;
+use crate::{
+    error::{Error, Result},
+    polynomials::Polynomial,
+};
+
+/// A test input applied from `t = 0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputSignal {
+    /// Dirac delta at `t = 0`, or a pulse of unit area `u[0] = 1 / T` in discrete time
+    Impulse,
+    /// Unit step
+    Step,
+    /// `u(t) = t`
+    Ramp,
+    /// `u(t) = t² / 2`
+    Parabola,
+}
+
+impl InputSignal {
+    /// Number of integrators turning the impulse into the input, `U(s) = 1 / s^n`
+    const fn integrators(self) -> usize {
+        match self {
+            Self::Impulse => 0,
+            Self::Step => 1,
+            Self::Ramp => 2,
+            Self::Parabola => 3,
+        }
+    }
+}
+
+/// A sample of a time response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimePoint<F> {
+    pub time: F,
+    pub value: F,
+}
+
+/// Derivative of the state of the controllable canonical form, `x' = A x`
+///
+/// `a` holds the denominator coefficients divided by the leading one, so that
+/// `x'ᵢ = xᵢ₊₁` and `x'ₙ = -Σ aᵢ xᵢ₊₁`
+fn companion_derivative<F: Float>(a: &[F], x: &[F], out: &mut [F]) {
+    let n = x.len();
+
+    out[..n - 1].copy_from_slice(&x[1..]);
+    out[n - 1] = -a.iter().zip(x).fold(F::zero(), |acc, (&a, &x)| acc + a * x);
+}
+
+impl<F: Float + Debug> TransferFunction<F> {
+    /// Simulates the response to `input` from `t = 0` to `final_time`, with `steps`
+    /// fixed steps of the classic Runge-Kutta method
+    ///
+    /// The response to `U(s) = 1 / s^n` is the impulse response of `G(s) / s^n`,
+    /// which is the output `C x` of its controllable canonical form starting
+    /// from `x(0) = B` with no input. The state is zero before `t = 0`.
+    ///
+    /// In discrete time the difference equation is run on the input sampled at
+    /// `t = nT`, up to `final_time`, and `steps` is ignored.
+    ///
+    /// # Errors
+    ///
+    /// `Error::ImproperSystem` if `G(s) / s^n` is not strictly proper, whose
+    /// response has Dirac impulses, or in discrete time if `G(z)` is not proper.
+    /// `Error::InvalidParameter` if the final time is not positive and finite, or
+    /// in continuous time if there are no steps
+    pub fn response(
+        &self,
+        input: InputSignal,
+        final_time: F,
+        steps: usize,
+    ) -> Result<Vec<TimePoint<F>>> {
+        if !final_time.is_finite() || final_time <= F::zero() {
+            return Err(Error::InvalidParameter(
+                "the final time must be positive and finite",
+            ));
+        }
+
+        if let Some(t) = self.sample_time {
+            return self.difference_response(input, final_time, t);
+        }
+
+        if steps == 0 {
+            return Err(Error::InvalidParameter("at least one step is needed"));
+        }
+
+        let dt = final_time / F::from(steps).unwrap();
+
+        if self.numerator.is_zero() {
+            return Ok((0..=steps)
+                .map(|i| TimePoint {
+                    time: dt * F::from(i).unwrap(),
+                    value: F::zero(),
+                })
+                .collect());
+        }
+
+        let mut den = vec![F::zero(); input.integrators()];
+        den.extend_from_slice(self.denominator.get_terms());
+        let den = Polynomial::new(den);
+
+        let n = den.order();
+        if self.numerator.order() >= n {
+            return Err(Error::ImproperSystem {
+                numerator_order: self.numerator.order(),
+                denominator_order: n,
+            });
+        }
+
+        let lead = den.get_terms()[n];
+        let a = den.get_terms()[..n]
+            .iter()
+            .map(|&x| x / lead)
+            .collect::<Vec<_>>();
+        let c = (0..n)
+            .map(|i| {
+                self.numerator
+                    .get_terms()
+                    .get(i)
+                    .copied()
+                    .unwrap_or_else(F::zero)
+                    / lead
+            })
+            .collect::<Vec<_>>();
+
+        let output = |x: &[F]| c.iter().zip(x).fold(F::zero(), |acc, (&c, &x)| acc + c * x);
+
+        let mut x = vec![F::zero(); n];
+        x[n - 1] = F::one();
+
+        let half = F::from(0.5).unwrap();
+        let sixth = F::one() / F::from(6).unwrap();
+        let two = F::from(2).unwrap();
+
+        let (mut k1, mut k2, mut k3, mut k4) = (
+            vec![F::zero(); n],
+            vec![F::zero(); n],
+            vec![F::zero(); n],
+            vec![F::zero(); n],
+        );
+        let mut tmp = vec![F::zero(); n];
+
+        let mut out = Vec::with_capacity(steps + 1);
+        out.push(TimePoint {
+            time: F::zero(),
+            value: output(&x),
+        });
+
+        for i in 1..=steps {
+            companion_derivative(&a, &x, &mut k1);
+
+            for j in 0..n {
+                tmp[j] = x[j] + k1[j] * dt * half;
+            }
+            companion_derivative(&a, &tmp, &mut k2);
+
+            for j in 0..n {
+                tmp[j] = x[j] + k2[j] * dt * half;
+            }
+            companion_derivative(&a, &tmp, &mut k3);
+
+            for j in 0..n {
+                tmp[j] = x[j] + k3[j] * dt;
+            }
+            companion_derivative(&a, &tmp, &mut k4);
+
+            for j in 0..n {
+                x[j] = x[j] + (k1[j] + two * (k2[j] + k3[j]) + k4[j]) * dt * sixth;
+            }
+
+            out.push(TimePoint {
+                time: dt * F::from(i).unwrap(),
+                value: output(&x),
+            });
+        }
+
+        Ok(out)
+    }
+
+    /// Runs `Σ dᵢ y[k - n + i] = Σ nᵢ u[k - n + i]`, with `n` the denominator order
+    fn difference_response(
+        &self,
+        input: InputSignal,
+        final_time: F,
+        sample_time: F,
+    ) -> Result<Vec<TimePoint<F>>> {
+        if !self.is_proper() {
+            return Err(Error::ImproperSystem {
+                numerator_order: self.numerator.order(),
+                denominator_order: self.denominator.order(),
+            });
+        }
+
+        // A final time on a sample, like 0.3 for T = 0.1, must keep that sample
+        let samples = (final_time / sample_time + F::epsilon().sqrt())
+            .floor()
+            .to_usize()
+            .unwrap();
+        let times = (0..=samples)
+            .map(|k| sample_time * F::from(k).unwrap())
+            .collect::<Vec<_>>();
+
+        let u = times
+            .iter()
+            .map(|&t| match input {
+                InputSignal::Impulse if t.is_zero() => sample_time.recip(),
+                InputSignal::Impulse => F::zero(),
+                InputSignal::Step => F::one(),
+                InputSignal::Ramp => t,
+                InputSignal::Parabola => t * t / F::from(2).unwrap(),
+            })
+            .collect::<Vec<_>>();
+
+        let num = self.numerator.get_terms();
+        let den = self.denominator.get_terms();
+        let n = self.denominator.order();
+
+        let mut y: Vec<F> = Vec::with_capacity(samples + 1);
+        for k in 0..=samples {
+            // Only the terms whose samples are at or after k = 0
+            let delayed = |i: usize| (k + i).checked_sub(n);
+
+            let forced = num.iter().enumerate().fold(F::zero(), |acc, (i, &x)| {
+                delayed(i).map_or(acc, |j| acc + x * u[j])
+            });
+            let free = den[..n].iter().enumerate().fold(F::zero(), |acc, (i, &x)| {
+                delayed(i).map_or(acc, |j| acc + x * y[j])
+            });
+
+            y.push((forced - free) / den[n]);
+        }
+
+        Ok(times
+            .into_iter()
+            .zip(y)
+            .map(|(time, value)| TimePoint { time, value })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        polynomials::{root_locus::RootLocus, Polynomial},
+        transfer_functions::discretization::Discretization,
+    };
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn first_order() {
+        // 1 / (s + 1)
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![1.0, 1.0]).unwrap();
+
+        let exact = |input, t: f64| match input {
+            InputSignal::Impulse => (-t).exp(),
+            InputSignal::Step => 1.0 - (-t).exp(),
+            InputSignal::Ramp => t - 1.0 + (-t).exp(),
+            InputSignal::Parabola => t * t / 2.0 - t + 1.0 - (-t).exp(),
+        };
+
+        for input in [
+            InputSignal::Impulse,
+            InputSignal::Step,
+            InputSignal::Ramp,
+            InputSignal::Parabola,
+        ] {
+            let response = g.response(input, 5.0, 500).unwrap();
+            assert_eq!(response.len(), 501);
+            for p in response {
+                assert_abs_diff_eq!(p.value, exact(input, p.time), epsilon = 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn biproper() {
+        // (s + 2) / (s + 1) jumps to 1 and settles at 2
+        let g = TransferFunction::from_coefficients(vec![2.0, 1.0], vec![1.0, 1.0]).unwrap();
+
+        let step = g.response(InputSignal::Step, 10.0, 1000).unwrap();
+        assert_abs_diff_eq!(step[0].value, 1.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(step.last().unwrap().value, 2.0, epsilon = 1e-3);
+
+        assert_eq!(
+            g.response(InputSignal::Impulse, 1.0, 10),
+            Err(Error::ImproperSystem {
+                numerator_order: 1,
+                denominator_order: 1
+            })
+        );
+
+        assert!(matches!(
+            g.response(InputSignal::Step, 0.0, 10),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            g.response(InputSignal::Step, 1.0, 0),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn discrete_zero_order_hold() {
+        // The zero-order hold keeps the step response of 1 / (s + 1) at the samples
+        let t = 0.1;
+        let g = TransferFunction::from_coefficients(vec![1.0], vec![1.0, 1.0])
+            .unwrap()
+            .c2d(t, Discretization::ZeroOrderHold)
+            .unwrap();
+
+        let step = g.response(InputSignal::Step, 5.0, 1).unwrap();
+        assert_eq!(step.len(), 51);
+        for p in step {
+            assert_abs_diff_eq!(p.value, 1.0 - (-p.time).exp(), epsilon = 1e-9);
+        }
+
+        // z / (z - 0.5) has the impulse response 0.5^k / T
+        let g = TransferFunction::from_coefficients(vec![0.0, 1.0], vec![-0.5, 1.0])
+            .unwrap()
+            .with_sample_time(Some(t));
+        let impulse = g.response(InputSignal::Impulse, 1.0, 1).unwrap();
+        for p in impulse {
+            assert_abs_diff_eq!(p.value, 0.5f64.powf(p.time / t) / t, epsilon = 1e-9);
+        }
+
+        // 0.3 / 0.1 is just below 3 in floating point
+        let step = g.response(InputSignal::Step, 0.3, 1).unwrap();
+        assert_eq!(step.len(), 4);
+
+        let improper = TransferFunction::from_coefficients(vec![0.0, 0.0, 1.0], vec![-0.5, 1.0])
+            .unwrap()
+            .with_sample_time(Some(t));
+        assert!(improper.response(InputSignal::Step, 1.0, 1).is_err());
+    }
+
+    #[test]
+    fn closed_loop_step() {
+        // 1 / (s (s + 1) (s + 2)) closed at k = 1 tracks a step
+        let a = Polynomial::new(vec![0.0, 2.0, 3.0, 1.0]);
+        let b = Polynomial::new(vec![1.0]);
+        let rl = RootLocus::new(a, b).unwrap();
+
+        let t = rl.closed_loop(1.0).unwrap();
+        assert_abs_diff_eq!(t.dc_gain(), 1.0);
+
+        let step = t.response(InputSignal::Step, 40.0, 4000).unwrap();
+        assert_abs_diff_eq!(step.last().unwrap().value, 1.0, epsilon = 1e-4);
+    }
+}